@@ -0,0 +1,252 @@
+//! Generates the rook/bishop magic-bitboard tables at build time instead of
+//! paying for the magic-number search and full occupancy-subset enumeration
+//! on every process startup. Mirrors the per-square mask/occupancy/attack
+//! math in `src/engine/movegen.rs` exactly (it has to stay a standalone
+//! copy: a build script compiles and runs before the crate it's building),
+//! and writes the result to `$OUT_DIR/magic_tables.rs`, which
+//! `MoveGen::init_magics` pulls in with `include!`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// xorshift64 PRNG used to draw magic-number candidates. Kept in lockstep
+/// with `movegen::MagicRng` so a square's generated magic is reproducible
+/// from its seed alone.
+struct MagicRng {
+    state: u64,
+}
+
+impl MagicRng {
+    fn new(seed: u64) -> Self {
+        MagicRng { state: if seed == 0 { 0x9E3779B9_7F4A7C15 } else { seed } }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+fn rook_mask(sq: u8) -> u64 {
+    let rank = sq / 8;
+    let file = sq % 8;
+    let mut mask = 0u64;
+
+    for r in (rank + 1)..7 {
+        mask |= 1u64 << (r * 8 + file);
+    }
+    for r in 1..rank {
+        mask |= 1u64 << (r * 8 + file);
+    }
+    for f in (file + 1)..7 {
+        mask |= 1u64 << (rank * 8 + f);
+    }
+    for f in 1..file {
+        mask |= 1u64 << (rank * 8 + f);
+    }
+
+    mask
+}
+
+fn bishop_mask(sq: u8) -> u64 {
+    let rank = sq / 8;
+    let file = sq % 8;
+    let mut mask = 0u64;
+
+    let mut r = rank + 1;
+    let mut f = file + 1;
+    while r < 7 && f < 7 {
+        mask |= 1u64 << (r * 8 + f);
+        r += 1;
+        f += 1;
+    }
+
+    r = rank.wrapping_sub(1);
+    f = file + 1;
+    while r > 0 && r < 8 && f < 7 {
+        mask |= 1u64 << (r * 8 + f);
+        r = r.wrapping_sub(1);
+        f += 1;
+    }
+
+    r = rank + 1;
+    f = file.wrapping_sub(1);
+    while r < 7 && f > 0 && f < 8 {
+        mask |= 1u64 << (r * 8 + f);
+        r += 1;
+        f = f.wrapping_sub(1);
+    }
+
+    r = rank.wrapping_sub(1);
+    f = file.wrapping_sub(1);
+    while r > 0 && r < 8 && f > 0 && f < 8 {
+        mask |= 1u64 << (r * 8 + f);
+        r = r.wrapping_sub(1);
+        f = f.wrapping_sub(1);
+    }
+
+    mask
+}
+
+fn index_to_occupancy(index: usize, mask: u64) -> u64 {
+    let mut occ = 0u64;
+    let mut m = mask;
+    let mut i = 0;
+
+    while m != 0 {
+        let sq = m.trailing_zeros();
+        m &= m - 1;
+        if (index >> i) & 1 != 0 {
+            occ |= 1u64 << sq;
+        }
+        i += 1;
+    }
+
+    occ
+}
+
+fn sliding_attacks(sq: u8, occ: u64, is_rook: bool) -> u64 {
+    let rank = sq / 8;
+    let file = sq % 8;
+    let mut attacks = 0u64;
+
+    let directions: [(i8, i8); 4] = if is_rook {
+        [(0, 1), (0, -1), (1, 0), (-1, 0)]
+    } else {
+        [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+    };
+
+    for (dr, df) in directions {
+        let mut r = rank as i8 + dr;
+        let mut f = file as i8 + df;
+
+        while r >= 0 && r < 8 && f >= 0 && f < 8 {
+            let target = (r * 8 + f) as u8;
+            attacks |= 1u64 << target;
+            if (occ >> target) & 1 != 0 {
+                break;
+            }
+            r += dr;
+            f += df;
+        }
+    }
+
+    attacks
+}
+
+/// Check whether `magic` is collision-free for `mask`: every occupancy
+/// subset must map to a slot that is either unused or already holds the
+/// identical attack set.
+fn magic_is_valid(sq: u8, mask: u64, magic: u64, is_rook: bool) -> bool {
+    let bits = mask.count_ones();
+    let size = 1usize << bits;
+    let mut table: Vec<Option<u64>> = vec![None; size];
+
+    for i in 0..size {
+        let occ = index_to_occupancy(i, mask);
+        let attacks = sliding_attacks(sq, occ, is_rook);
+        let idx = ((occ.wrapping_mul(magic)) >> (64 - bits)) as usize;
+
+        match table[idx] {
+            None => table[idx] = Some(attacks),
+            Some(existing) if existing == attacks => {}
+            Some(_) => return false,
+        }
+    }
+
+    true
+}
+
+/// Search for a collision-free magic number for `sq`, drawing sparse
+/// candidates (the AND of three random draws tends to have few set bits,
+/// which hashes occupancies well).
+fn find_magic(sq: u8, mask: u64, is_rook: bool) -> u64 {
+    let mut rng = MagicRng::new(0x9E3779B9_7F4A7C15 ^ (sq as u64) << 1 ^ (is_rook as u64));
+
+    loop {
+        let magic = rng.next() & rng.next() & rng.next();
+
+        if (mask.wrapping_mul(magic) >> 56).count_ones() < 6 {
+            continue;
+        }
+
+        if magic_is_valid(sq, mask, magic, is_rook) {
+            return magic;
+        }
+    }
+}
+
+/// Build one side's (rook or bishop) full table set: masks, magics, shifts,
+/// per-square offsets into a single flattened attack array, and the
+/// attack array itself.
+#[allow(clippy::type_complexity)]
+fn build_tables(is_rook: bool) -> ([u64; 64], [u64; 64], [u32; 64], [usize; 64], Vec<u64>) {
+    let mut masks = [0u64; 64];
+    let mut magics = [0u64; 64];
+    let mut shifts = [0u32; 64];
+    let mut offsets = [0usize; 64];
+    let mut table = Vec::new();
+
+    for sq in 0u8..64 {
+        let mask = if is_rook { rook_mask(sq) } else { bishop_mask(sq) };
+        let bits = mask.count_ones();
+        let size = 1usize << bits;
+        let magic = find_magic(sq, mask, is_rook);
+
+        masks[sq as usize] = mask;
+        magics[sq as usize] = magic;
+        shifts[sq as usize] = 64 - bits;
+        offsets[sq as usize] = table.len();
+
+        let mut slot = vec![0u64; size];
+        for i in 0..size {
+            let occ = index_to_occupancy(i, mask);
+            let attacks = sliding_attacks(sq, occ, is_rook);
+            let idx = ((occ.wrapping_mul(magic)) >> (64 - bits)) as usize;
+            slot[idx] = attacks;
+        }
+        table.extend_from_slice(&slot);
+    }
+
+    (masks, magics, shifts, offsets, table)
+}
+
+fn write_array(out: &mut String, name: &str, ty: &str, len: usize, values: &[u64]) {
+    let _ = writeln!(out, "pub const {name}: [{ty}; {len}] = [");
+    for chunk in values.chunks(8) {
+        let line: Vec<String> = chunk.iter().map(|v| format!("0x{v:016X}")).collect();
+        let _ = writeln!(out, "    {},", line.join(", "));
+    }
+    let _ = writeln!(out, "];");
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let (rook_masks, rook_magics, rook_shifts, rook_offsets, rook_table) = build_tables(true);
+    let (bishop_masks, bishop_magics, bishop_shifts, bishop_offsets, bishop_table) =
+        build_tables(false);
+
+    let mut out = String::new();
+    out.push_str("// @generated by build.rs - magic bitboard tables for sliding-piece attacks.\n\n");
+
+    write_array(&mut out, "ROOK_MASKS", "u64", 64, &rook_masks);
+    write_array(&mut out, "ROOK_MAGICS", "u64", 64, &rook_magics);
+    let _ = writeln!(out, "pub const ROOK_SHIFTS: [u32; 64] = {:?};", rook_shifts);
+    let _ = writeln!(out, "pub const ROOK_OFFSETS: [usize; 64] = {:?};", rook_offsets);
+    write_array(&mut out, "ROOK_ATTACK_TABLE", "u64", rook_table.len(), &rook_table);
+
+    write_array(&mut out, "BISHOP_MASKS", "u64", 64, &bishop_masks);
+    write_array(&mut out, "BISHOP_MAGICS", "u64", 64, &bishop_magics);
+    let _ = writeln!(out, "pub const BISHOP_SHIFTS: [u32; 64] = {:?};", bishop_shifts);
+    let _ = writeln!(out, "pub const BISHOP_OFFSETS: [usize; 64] = {:?};", bishop_offsets);
+    write_array(&mut out, "BISHOP_ATTACK_TABLE", "u64", bishop_table.len(), &bishop_table);
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    fs::write(Path::new(&out_dir).join("magic_tables.rs"), out)
+        .expect("failed to write generated magic tables");
+}
@@ -6,6 +6,20 @@
 use std::fmt;
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr};
 
+/// One of the eight compass directions a sliding piece can travel,
+/// matching `Bitboard`'s existing per-direction shift methods.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
 /// A 64-bit bitboard representing squares on the chess board
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub struct Bitboard(pub u64);
@@ -164,6 +178,22 @@ impl Bitboard {
         Bitboard((self.0 >> 9) & !Self::FILE_H.0)
     }
 
+    /// Shift the bitboard one step in `dir`, dispatching to the matching
+    /// per-direction method.
+    #[inline]
+    pub const fn shift(self, dir: Direction) -> Self {
+        match dir {
+            Direction::North => self.north(),
+            Direction::South => self.south(),
+            Direction::East => self.east(),
+            Direction::West => self.west(),
+            Direction::NorthEast => self.north_east(),
+            Direction::NorthWest => self.north_west(),
+            Direction::SouthEast => self.south_east(),
+            Direction::SouthWest => self.south_west(),
+        }
+    }
+
     /// Get file mask for a given file index (0-7)
     #[inline]
     pub const fn file_mask(file: u8) -> Self {
@@ -206,6 +236,15 @@ impl Iterator for BitboardIter {
 
 impl ExactSizeIterator for BitboardIter {}
 
+impl IntoIterator for Bitboard {
+    type Item = u8;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 // Implement bitwise operations
 impl BitAnd for Bitboard {
     type Output = Self;
@@ -302,6 +341,80 @@ impl fmt::Display for Bitboard {
     }
 }
 
+/// The full ray of squares from `sq` in `dir`, repeatedly shifting until it
+/// leaves the board. Excludes `sq` itself.
+pub fn ray(sq: u8, dir: Direction) -> Bitboard {
+    let mut result = Bitboard::EMPTY;
+    let mut step = Bitboard::from_square(sq).shift(dir);
+    while step.is_not_empty() {
+        result |= step;
+        step = step.shift(dir);
+    }
+    result
+}
+
+/// The exclusive set of squares on the line connecting `a` and `b`, or
+/// empty if they aren't aligned on a rank, file, or diagonal. Delegates to
+/// `engine::movegen`'s precomputed table (see [`magic`] for why).
+pub fn between(a: u8, b: u8) -> Bitboard {
+    crate::engine::movegen::MoveGen::between(crate::core::board::Square(a), crate::core::board::Square(b))
+}
+
+/// The full line through `a` and `b` (both endpoints included), or empty
+/// if they aren't aligned on a rank, file, or diagonal.
+pub fn line(a: u8, b: u8) -> Bitboard {
+    crate::engine::movegen::MoveGen::line(crate::core::board::Square(a), crate::core::board::Square(b))
+}
+
+/// Sliding-piece attack lookups, keyed by raw `u8` square index instead of
+/// `engine::movegen`'s `Square`-typed API. The magic-bitboard tables
+/// themselves — generated at build time by `build.rs` per chunk5-4 and
+/// owned by [`crate::engine::movegen::MoveGen`] — aren't duplicated here;
+/// this just gives bitboard-only core code a way to query them without
+/// round-tripping through `Square`.
+pub mod magic {
+    use super::Bitboard;
+    use crate::core::board::Square;
+    use crate::engine::movegen::MoveGen;
+
+    /// Rook attacks from `sq` given the current occupancy.
+    #[inline]
+    pub fn rook_attacks(sq: u8, occupied: Bitboard) -> Bitboard {
+        MoveGen::rook_attacks(Square(sq), occupied)
+    }
+
+    /// Bishop attacks from `sq` given the current occupancy.
+    #[inline]
+    pub fn bishop_attacks(sq: u8, occupied: Bitboard) -> Bitboard {
+        MoveGen::bishop_attacks(Square(sq), occupied)
+    }
+
+    /// Queen attacks from `sq` given the current occupancy (rook ∪ bishop).
+    #[inline]
+    pub fn queen_attacks(sq: u8, occupied: Bitboard) -> Bitboard {
+        MoveGen::queen_attacks(Square(sq), occupied)
+    }
+}
+
+/// Knight attacks from `sq`, via `engine::movegen`'s precomputed table.
+#[inline]
+pub fn knight_attacks(sq: u8) -> Bitboard {
+    crate::engine::movegen::MoveGen::knight_attacks(crate::core::board::Square(sq))
+}
+
+/// King attacks from `sq`, via `engine::movegen`'s precomputed table.
+#[inline]
+pub fn king_attacks(sq: u8) -> Bitboard {
+    crate::engine::movegen::MoveGen::king_attacks(crate::core::board::Square(sq))
+}
+
+/// Pawn attacks from `sq` for `color`, via `engine::movegen`'s precomputed
+/// table.
+#[inline]
+pub fn pawn_attacks(color: crate::core::board::Color, sq: u8) -> Bitboard {
+    crate::engine::movegen::MoveGen::pawn_attacks(crate::core::board::Square(sq), color)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,5 +496,82 @@ mod tests {
         assert_eq!((a ^ b).0, 0b0110);
         assert_eq!((!Bitboard::EMPTY).0, !0u64);
     }
+
+    #[test]
+    fn test_magic_rook_attacks_can_be_iterated_with_for_loop() {
+        // `for sq in bb` (via `IntoIterator`) should yield the same
+        // squares as manually draining `.iter()`.
+        let attacks = magic::rook_attacks(0, Bitboard::EMPTY);
+        let mut squares = Vec::new();
+        for sq in attacks {
+            squares.push(sq);
+        }
+        assert_eq!(squares, attacks.iter().collect::<Vec<_>>());
+        assert_eq!(squares.len(), 14);
+    }
+
+    #[test]
+    fn test_magic_rook_attacks_on_empty_board() {
+        // Rook on a1 with nothing else on the board sees its whole rank
+        // and file, minus its own square.
+        let attacks = magic::rook_attacks(0, Bitboard::EMPTY);
+        assert_eq!(attacks, (Bitboard::RANK_1 | Bitboard::FILE_A) & !Bitboard::from_square(0));
+    }
+
+    #[test]
+    fn test_magic_queen_attacks_is_rook_or_bishop() {
+        let occ = Bitboard::from_square(27); // d4
+        let queen = magic::queen_attacks(27, occ);
+        let rook = magic::rook_attacks(27, occ);
+        let bishop = magic::bishop_attacks(27, occ);
+        assert_eq!(queen, rook | bishop);
+    }
+
+    #[test]
+    fn test_knight_and_king_attacks_from_corner() {
+        // A knight on a1 only reaches b3 and c2.
+        let knight = knight_attacks(0);
+        assert_eq!(knight.count(), 2);
+        assert!(knight.contains(17)); // b3
+        assert!(knight.contains(10)); // c2
+
+        // A king on a1 only reaches its three neighbours.
+        let king = king_attacks(0);
+        assert_eq!(king.count(), 3);
+    }
+
+    #[test]
+    fn test_pawn_attacks_are_color_specific() {
+        use crate::core::board::Color;
+
+        // A white pawn on e4 attacks d5/f5; a black pawn on e4 attacks d3/f3.
+        let white = pawn_attacks(Color::White, 28);
+        let black = pawn_attacks(Color::Black, 28);
+        assert_ne!(white, black);
+        assert_eq!(white.count(), 2);
+        assert_eq!(black.count(), 2);
+    }
+
+    #[test]
+    fn test_ray_stops_at_board_edge() {
+        let north = ray(0, Direction::North); // a1 north: a2..a8
+        assert_eq!(north, Bitboard::FILE_A & !Bitboard::from_square(0));
+
+        assert_eq!(ray(0, Direction::West), Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn test_between_and_line() {
+        // a1-h8 diagonal: squares strictly between a1 and d4 are b2, c3.
+        let a1 = 0;
+        let d4 = 27;
+        let squares_between = between(a1, d4);
+        assert_eq!(squares_between.count(), 2);
+        assert!(squares_between.contains(9)); // b2
+        assert!(squares_between.contains(18)); // c3
+
+        assert!(line(a1, d4).contains(63)); // h8, same diagonal
+        assert_eq!(between(a1, 1), Bitboard::EMPTY); // a1/b1 adjacent, nothing between
+    }
 }
 
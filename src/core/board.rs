@@ -142,6 +142,13 @@ impl Square {
     pub const fn flip_vertical(self) -> Self {
         Square(self.0 ^ 56)
     }
+
+    /// Mirror the square horizontally (a-file <-> h-file), used to exploit
+    /// left/right board symmetry (e.g. NNUE king-bucket indexing).
+    #[inline]
+    pub const fn flip_horizontal(self) -> Self {
+        Square(self.0 ^ 7)
+    }
 }
 
 impl fmt::Display for Square {
@@ -302,6 +309,24 @@ impl Piece {
         Some(Piece::new(piece_type, color))
     }
 
+    /// Get the Unicode chess glyph for this piece, for terminal rendering.
+    pub fn to_unicode(self) -> char {
+        match (self.color, self.piece_type) {
+            (Color::White, PieceType::Pawn) => '♙',
+            (Color::White, PieceType::Knight) => '♘',
+            (Color::White, PieceType::Bishop) => '♗',
+            (Color::White, PieceType::Rook) => '♖',
+            (Color::White, PieceType::Queen) => '♕',
+            (Color::White, PieceType::King) => '♔',
+            (Color::Black, PieceType::Pawn) => '♟',
+            (Color::Black, PieceType::Knight) => '♞',
+            (Color::Black, PieceType::Bishop) => '♝',
+            (Color::Black, PieceType::Rook) => '♜',
+            (Color::Black, PieceType::Queen) => '♛',
+            (Color::Black, PieceType::King) => '♚',
+        }
+    }
+
     /// Get piece index for NNUE (0-11)
     #[inline]
     pub fn nnue_index(self) -> usize {
@@ -369,6 +394,107 @@ impl CastlingRights {
     }
 }
 
+/// Castling rule variant. `Standard` assumes the king starts on the
+/// e-file and the rooks on the a-/h-files, so `generate_castling_moves`
+/// can use a fixed fast path. `Chess960` computes the king's and rook's
+/// travel paths from wherever `castling_king_files`/`castling_rook_files`
+/// say they actually started, per Fischer Random rules.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CastlingMode {
+    #[default]
+    Standard,
+    Chess960,
+}
+
+/// Which game-termination rules [`Board::terminal_state`] applies.
+/// `Standard` adjudicates checkmate, stalemate, and insufficient material
+/// the usual way. `DuckChess` has no concept of check: the duck can block
+/// a king in behind its own pieces with nothing resembling a mating net,
+/// so the variant is decided purely by king capture, with a side that has
+/// no legal move simply losing on the spot.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Variant {
+    #[default]
+    Standard,
+    DuckChess,
+}
+
+/// The outcome of a position, as adjudicated by [`Board::terminal_state`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TerminalState {
+    Ongoing,
+    Win(Color),
+    Draw,
+}
+
+/// State needed to reverse a [`Board::make_move`] in place, returned by
+/// `make_move` and consumed by [`Board::unmake_move`]. Keeping this on a
+/// caller-owned stack instead of cloning the whole `Board` per move turns
+/// the per-node cost of `perft`/search from a full board copy into a
+/// handful of field writes.
+#[derive(Clone, Copy, Debug)]
+pub struct UndoInfo {
+    moved_piece: Piece,
+    captured: Option<(Piece, Square)>,
+    castling: CastlingRights,
+    en_passant: Option<Square>,
+    halfmove_clock: u8,
+    fullmove_number: u16,
+    duck: Option<Square>,
+    hash: u64,
+    pawn_hash: u64,
+    checkers: Bitboard,
+}
+
+/// Why a position failed [`Board::is_valid`]. Modeled on seer's validator:
+/// FEN parsing only checks syntax, so a caller that needs a legal starting
+/// position (the UCI `position fen` handler, test fixtures) should also
+/// check this and report which rule was broken.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvalidError {
+    /// A color has more than one king.
+    TooManyKings,
+    /// A color has no king at all.
+    MissingKing,
+    /// A pawn sits on rank 1 or rank 8, which is impossible to reach.
+    PawnOnBackRank,
+    /// The two kings are on adjacent squares, which is impossible since
+    /// neither could have moved there without being in check.
+    NeighbouringKings,
+    /// The side not to move is in check, meaning their opponent's last
+    /// move left their own king attacked.
+    OppositeCheck,
+    /// A castling-rights flag is set but the king or rook isn't on its
+    /// recorded home square.
+    InvalidCastlingRights,
+    /// The en passant square isn't empty, isn't on the rank a just-moved
+    /// double push would land behind, or has no opposing pawn in front of
+    /// it to have made that push.
+    InvalidEnPassant,
+    /// The duck sits on a square that's also occupied by a piece.
+    DuckOnOccupiedSquare,
+}
+
+impl fmt::Display for InvalidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            InvalidError::TooManyKings => "a color cannot have more than one king",
+            InvalidError::MissingKing => "each side must have a king",
+            InvalidError::PawnOnBackRank => "pawns cannot sit on rank 1 or rank 8",
+            InvalidError::NeighbouringKings => "kings cannot stand on adjacent squares",
+            InvalidError::OppositeCheck => "the side not to move is in check",
+            InvalidError::InvalidCastlingRights => {
+                "castling rights don't match the king/rook placement"
+            }
+            InvalidError::InvalidEnPassant => "en passant square is inconsistent with the position",
+            InvalidError::DuckOnOccupiedSquare => "the duck cannot share a square with a piece",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::error::Error for InvalidError {}
+
 /// The chess board state
 #[derive(Clone)]
 pub struct Board {
@@ -390,8 +516,32 @@ pub struct Board {
     pub fullmove_number: u16,
     /// Zobrist hash of the position
     pub hash: u64,
+    /// Zobrist hash of just the pawn and king placements, updated
+    /// incrementally alongside `hash`. Pawn skeletons change far less
+    /// often than the rest of the position, so an evaluation layer can
+    /// key a pawn-structure cache off this instead of the full `hash` and
+    /// get a much higher hit rate.
+    pub pawn_hash: u64,
     /// Checkers (pieces giving check)
     pub checkers: Bitboard,
+    /// The duck's current square, if it has been placed. The duck is a
+    /// neutral blocker that belongs to neither color, occupies the board
+    /// like any other piece, and can never be captured.
+    pub duck: Option<Square>,
+    /// Whether castling follows the classical e/a/h-file layout or
+    /// Fischer Random's arbitrary starting files.
+    pub castling_mode: CastlingMode,
+    /// The king's starting file for each color, indexed by `Color::index()`.
+    /// Only consulted under `CastlingMode::Chess960`.
+    pub castling_king_files: [u8; 2],
+    /// The rook's starting file for each color and side, indexed by
+    /// `[Color::index()][0 = kingside, 1 = queenside]`. Only consulted
+    /// under `CastlingMode::Chess960`.
+    pub castling_rook_files: [[u8; 2]; 2],
+    /// Which rule set [`Board::terminal_state`] adjudicates under. Separate
+    /// from `castling_mode` because it governs win/draw conditions rather
+    /// than move generation.
+    pub variant: Variant,
 }
 
 impl Board {
@@ -407,7 +557,13 @@ impl Board {
             halfmove_clock: 0,
             fullmove_number: 1,
             hash: 0,
+            pawn_hash: 0,
             checkers: Bitboard::EMPTY,
+            duck: None,
+            castling_mode: CastlingMode::Standard,
+            castling_king_files: [4, 4],
+            castling_rook_files: [[7, 0], [7, 0]],
+            variant: Variant::Standard,
         }
     }
 
@@ -416,9 +572,11 @@ impl Board {
         Self::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap()
     }
 
-    /// Parse a board from FEN notation
+    /// Parse a board from FEN notation. Assembles a [`BoardBuilder`]
+    /// internally so parsing and validation go through the same single
+    /// path as programmatic construction.
     pub fn from_fen(fen: &str) -> Result<Self, String> {
-        let mut board = Board::empty();
+        let mut builder = BoardBuilder::new();
         let parts: Vec<&str> = fen.split_whitespace().collect();
 
         if parts.len() < 4 {
@@ -437,7 +595,7 @@ impl Board {
                 }
                 _ => {
                     if let Some(piece) = Piece::from_char(c) {
-                        board.put_piece(piece, Square(sq as u8));
+                        builder = builder.piece(piece, Square(sq as u8));
                         sq += 1;
                     } else {
                         return Err(format!("Invalid FEN: unknown piece '{}'", c));
@@ -447,51 +605,180 @@ impl Board {
         }
 
         // Parse side to move
-        board.side_to_move = match parts[1] {
+        builder = builder.side_to_move(match parts[1] {
             "w" => Color::White,
             "b" => Color::Black,
             _ => return Err("Invalid FEN: invalid side to move".to_string()),
-        };
+        });
 
-        // Parse castling rights
+        // Parse castling rights. Accepts both standard `KQkq` and Shredder-FEN
+        // (e.g. `HAha`), which names the actual rook files and is how Chess960
+        // games are distinguished from standard ones: the instant we see a
+        // letter outside `KQkq-`, it's a rook file and the game is Chess960.
         let mut castling = 0u8;
-        for c in parts[2].chars() {
-            match c {
-                'K' => castling |= CastlingRights::WHITE_KINGSIDE,
-                'Q' => castling |= CastlingRights::WHITE_QUEENSIDE,
-                'k' => castling |= CastlingRights::BLACK_KINGSIDE,
-                'q' => castling |= CastlingRights::BLACK_QUEENSIDE,
-                '-' => {}
-                _ => return Err(format!("Invalid FEN: unknown castling right '{}'", c)),
+        let mut castling_mode = CastlingMode::Standard;
+        let mut castling_king_files = [4u8, 4u8];
+        let mut castling_rook_files = [[7u8, 0u8], [7u8, 0u8]];
+        if parts[2] != "-" {
+            castling_king_files[Color::White.index()] = builder.king_square(Color::White).file();
+            castling_king_files[Color::Black.index()] = builder.king_square(Color::Black).file();
+
+            for c in parts[2].chars() {
+                match c {
+                    'K' => castling |= CastlingRights::WHITE_KINGSIDE,
+                    'Q' => castling |= CastlingRights::WHITE_QUEENSIDE,
+                    'k' => castling |= CastlingRights::BLACK_KINGSIDE,
+                    'q' => castling |= CastlingRights::BLACK_QUEENSIDE,
+                    'A'..='H' => {
+                        castling_mode = CastlingMode::Chess960;
+                        let file = c as u8 - b'A';
+                        let kingside = file > castling_king_files[Color::White.index()];
+                        let side = if kingside { 0 } else { 1 };
+                        castling_rook_files[Color::White.index()][side] = file;
+                        castling |= if kingside {
+                            CastlingRights::WHITE_KINGSIDE
+                        } else {
+                            CastlingRights::WHITE_QUEENSIDE
+                        };
+                    }
+                    'a'..='h' => {
+                        castling_mode = CastlingMode::Chess960;
+                        let file = c as u8 - b'a';
+                        let kingside = file > castling_king_files[Color::Black.index()];
+                        let side = if kingside { 0 } else { 1 };
+                        castling_rook_files[Color::Black.index()][side] = file;
+                        castling |= if kingside {
+                            CastlingRights::BLACK_KINGSIDE
+                        } else {
+                            CastlingRights::BLACK_QUEENSIDE
+                        };
+                    }
+                    _ => return Err(format!("Invalid FEN: unknown castling right '{}'", c)),
+                }
             }
         }
-        board.castling = CastlingRights::new(castling);
+        builder = builder
+            .castling(CastlingRights::new(castling))
+            .castling_mode(castling_mode, castling_king_files, castling_rook_files);
 
         // Parse en passant
-        board.en_passant = if parts[3] == "-" {
+        builder = builder.en_passant(if parts[3] == "-" {
             None
         } else {
             Some(Square::from_algebraic(parts[3])
                 .ok_or_else(|| format!("Invalid FEN: invalid en passant square '{}'", parts[3]))?)
-        };
+        });
 
         // Parse halfmove clock
         if parts.len() > 4 {
-            board.halfmove_clock = parts[4].parse().unwrap_or(0);
+            builder = builder.halfmove_clock(parts[4].parse().unwrap_or(0));
         }
 
         // Parse fullmove number
         if parts.len() > 5 {
-            board.fullmove_number = parts[5].parse().unwrap_or(1);
+            builder = builder.fullmove_number(parts[5].parse().unwrap_or(1));
+        }
+
+        // Parse the duck's square, a seventh field with no standard-FEN
+        // equivalent. Plain chess FEN (six fields) parses with no duck.
+        if parts.len() > 6 && parts[6] != "-" {
+            builder = builder.duck(Some(
+                Square::from_algebraic(parts[6])
+                    .ok_or_else(|| format!("Invalid FEN: invalid duck square '{}'", parts[6]))?,
+            ));
+        }
+
+        builder.build().map_err(|e| format!("Invalid FEN: {}", e))
+    }
+
+    /// Reject positions that are syntactically valid FEN but cannot arise
+    /// from a legal game: wrong king counts, pawns on the back rank,
+    /// kings standing next to each other, the side not to move already in
+    /// check, castling rights that don't match where the king/rook
+    /// actually are, or an en passant square that couldn't have just been
+    /// created by a double push. Modeled on seer's position validator.
+    pub fn is_valid(&self) -> Result<(), InvalidError> {
+        for color in [Color::White, Color::Black] {
+            match self.pieces_of(PieceType::King, color).count() {
+                0 => return Err(InvalidError::MissingKing),
+                1 => {}
+                _ => return Err(InvalidError::TooManyKings),
+            }
+        }
+
+        let back_ranks = Bitboard::rank_mask(0) | Bitboard::rank_mask(7);
+        if (self.pieces(PieceType::Pawn) & back_ranks).is_not_empty() {
+            return Err(InvalidError::PawnOnBackRank);
+        }
+
+        let white_king = self.king_square(Color::White);
+        let black_king = self.king_square(Color::Black);
+        let file_dist = (white_king.file() as i8 - black_king.file() as i8).abs();
+        let rank_dist = (white_king.rank() as i8 - black_king.rank() as i8).abs();
+        if file_dist <= 1 && rank_dist <= 1 {
+            return Err(InvalidError::NeighbouringKings);
         }
 
-        // Calculate hash
-        board.hash = board.calculate_hash();
+        let opponent = self.side_to_move.opposite();
+        if self
+            .attackers_to(self.king_square(opponent), self.side_to_move)
+            .is_not_empty()
+        {
+            return Err(InvalidError::OppositeCheck);
+        }
 
-        // Calculate checkers
-        board.update_checkers();
+        for color in [Color::White, Color::Black] {
+            let back_rank = color.opposite().promotion_rank();
+            let king_home = self.castling_king_files[color.index()];
+            if self.castling.can_castle_kingside(color) || self.castling.can_castle_queenside(color) {
+                if self.piece_at[Square::from_file_rank(king_home, back_rank).index()]
+                    != Some(Piece::new(PieceType::King, color))
+                {
+                    return Err(InvalidError::InvalidCastlingRights);
+                }
+            }
+            if self.castling.can_castle_kingside(color) {
+                let rook_file = self.castling_rook_files[color.index()][0];
+                if self.piece_at[Square::from_file_rank(rook_file, back_rank).index()]
+                    != Some(Piece::new(PieceType::Rook, color))
+                {
+                    return Err(InvalidError::InvalidCastlingRights);
+                }
+            }
+            if self.castling.can_castle_queenside(color) {
+                let rook_file = self.castling_rook_files[color.index()][1];
+                if self.piece_at[Square::from_file_rank(rook_file, back_rank).index()]
+                    != Some(Piece::new(PieceType::Rook, color))
+                {
+                    return Err(InvalidError::InvalidCastlingRights);
+                }
+            }
+        }
+
+        if let Some(duck) = self.duck {
+            if self.piece_at[duck.index()].is_some() {
+                return Err(InvalidError::DuckOnOccupiedSquare);
+            }
+        }
 
-        Ok(board)
+        if let Some(ep) = self.en_passant {
+            if self.piece_at[ep.index()].is_some() {
+                return Err(InvalidError::InvalidEnPassant);
+            }
+            let mover = self.side_to_move.opposite();
+            let pushed_rank = self.side_to_move.en_passant_rank();
+            let expected_ep_rank =
+                (pushed_rank as i8 + self.side_to_move.pawn_direction() / 8) as u8;
+            if ep.rank() != expected_ep_rank {
+                return Err(InvalidError::InvalidEnPassant);
+            }
+            let pushed_pawn = Square::from_file_rank(ep.file(), pushed_rank);
+            if self.piece_at[pushed_pawn.index()] != Some(Piece::new(PieceType::Pawn, mover)) {
+                return Err(InvalidError::InvalidEnPassant);
+            }
+        }
+
+        Ok(())
     }
 
     /// Convert the board to FEN notation
@@ -528,22 +815,42 @@ impl Board {
             Color::Black => 'b',
         });
 
-        // Castling rights
+        // Castling rights. Standard games emit classical `KQkq`; Chess960
+        // games emit Shredder-FEN rook-file letters (e.g. `HAha`) so the
+        // rook's actual starting file survives the round trip.
         fen.push(' ');
         if self.castling.0 == 0 {
             fen.push('-');
         } else {
-            if self.castling.has(CastlingRights::WHITE_KINGSIDE) {
-                fen.push('K');
-            }
-            if self.castling.has(CastlingRights::WHITE_QUEENSIDE) {
-                fen.push('Q');
-            }
-            if self.castling.has(CastlingRights::BLACK_KINGSIDE) {
-                fen.push('k');
-            }
-            if self.castling.has(CastlingRights::BLACK_QUEENSIDE) {
-                fen.push('q');
+            match self.castling_mode {
+                CastlingMode::Standard => {
+                    if self.castling.has(CastlingRights::WHITE_KINGSIDE) {
+                        fen.push('K');
+                    }
+                    if self.castling.has(CastlingRights::WHITE_QUEENSIDE) {
+                        fen.push('Q');
+                    }
+                    if self.castling.has(CastlingRights::BLACK_KINGSIDE) {
+                        fen.push('k');
+                    }
+                    if self.castling.has(CastlingRights::BLACK_QUEENSIDE) {
+                        fen.push('q');
+                    }
+                }
+                CastlingMode::Chess960 => {
+                    if self.castling.has(CastlingRights::WHITE_KINGSIDE) {
+                        fen.push((b'A' + self.castling_rook_files[Color::White.index()][0]) as char);
+                    }
+                    if self.castling.has(CastlingRights::WHITE_QUEENSIDE) {
+                        fen.push((b'A' + self.castling_rook_files[Color::White.index()][1]) as char);
+                    }
+                    if self.castling.has(CastlingRights::BLACK_KINGSIDE) {
+                        fen.push((b'a' + self.castling_rook_files[Color::Black.index()][0]) as char);
+                    }
+                    if self.castling.has(CastlingRights::BLACK_QUEENSIDE) {
+                        fen.push((b'a' + self.castling_rook_files[Color::Black.index()][1]) as char);
+                    }
+                }
             }
         }
 
@@ -560,6 +867,15 @@ impl Board {
         fen.push(' ');
         fen.push_str(&self.fullmove_number.to_string());
 
+        // Duck's square, as a seventh field with no standard-FEN
+        // equivalent. Omitted entirely when the duck hasn't been placed,
+        // so a duck-less position still round-trips through plain
+        // six-field chess FEN byte-for-byte.
+        if let Some(sq) = self.duck {
+            fen.push(' ');
+            fen.push_str(&sq.to_algebraic());
+        }
+
         fen
     }
 
@@ -606,10 +922,40 @@ impl Board {
         self.pieces[piece_type.index()] & self.colors[color.index()]
     }
 
-    /// Get all occupied squares
+    /// `Some(true)`/`Some(false)` for whether `color`'s lone bishop sits on
+    /// a light/dark square, or `None` if that side doesn't have exactly
+    /// one bishop. Used by eval's opposite-colored-bishop draw detection,
+    /// where it's the parity mismatch between the two sides that matters.
+    #[inline]
+    pub fn bishop_color_parity(&self, color: Color) -> Option<bool> {
+        let bishops = self.pieces_of(PieceType::Bishop, color);
+        if bishops.count() != 1 {
+            return None;
+        }
+        Some((bishops & Bitboard::LIGHT_SQUARES).is_not_empty())
+    }
+
+    /// Get all occupied squares, including the duck
     #[inline]
     pub fn occupied(&self) -> Bitboard {
-        self.colors[0] | self.colors[1]
+        self.colors[0] | self.colors[1] | self.duck_bitboard()
+    }
+
+    /// Count of pieces on the board (both colors, kings included, duck
+    /// excluded), the material-size figure tablebase probing gates on.
+    #[inline]
+    pub fn piece_count(&self) -> u32 {
+        (self.colors[0] | self.colors[1]).count()
+    }
+
+    /// Get the duck's square as a bitboard, or empty if it hasn't been
+    /// placed yet
+    #[inline]
+    pub fn duck_bitboard(&self) -> Bitboard {
+        match self.duck {
+            Some(sq) => Bitboard::from_square(sq.0),
+            None => Bitboard::EMPTY,
+        }
     }
 
     /// Get the king square for a color
@@ -649,9 +995,45 @@ impl Board {
             hash ^= keys.en_passant(ep);
         }
 
+        // Hash the duck's square, so two positions that differ only in
+        // where the duck sits are never confused for the same one.
+        hash ^= keys.duck(self.duck);
+
         hash
     }
 
+    /// The position's Zobrist hash, maintained incrementally by
+    /// [`Board::make_move_unchecked`]/[`Board::unmake_move`]. Feeds the
+    /// transposition table and repetition detection.
+    #[inline]
+    pub fn zobrist(&self) -> u64 {
+        self.hash
+    }
+
+    /// Calculate the pawn-structure Zobrist hash from scratch: just the
+    /// pawn and king placements, with no side-to-move, castling, or en
+    /// passant component. See `pawn_hash`.
+    pub fn calculate_pawn_hash(&self) -> u64 {
+        let keys = ZobristKeys::instance();
+        let mut hash = 0u64;
+
+        for sq in 0..64 {
+            if let Some(piece) = self.piece_at[sq] {
+                if Self::is_pawn_hash_piece(piece.piece_type) {
+                    hash ^= keys.piece(piece, Square(sq as u8));
+                }
+            }
+        }
+
+        hash
+    }
+
+    /// Whether a piece type is tracked by `pawn_hash`.
+    #[inline]
+    fn is_pawn_hash_piece(piece_type: PieceType) -> bool {
+        matches!(piece_type, PieceType::Pawn | PieceType::King)
+    }
+
     /// Update the checkers bitboard
     pub fn update_checkers(&mut self) {
         self.checkers = self.attackers_to(self.king_square(self.side_to_move), self.side_to_move.opposite());
@@ -684,18 +1066,163 @@ impl Board {
         self.attackers_to(sq, by_color).is_not_empty()
     }
 
-    /// Make a move on the board (returns true if legal)
-    pub fn make_move(&mut self, mv: Move) -> bool {
+    /// The castling rights that should be removed when a piece moves
+    /// from/to `sq`. Under `CastlingMode::Chess960` this consults the
+    /// actual starting files instead of the hardcoded classical squares,
+    /// since the king and rooks can start anywhere on the back rank.
+    fn castling_rights_for_square(&self, sq: Square) -> u8 {
+        match self.castling_mode {
+            CastlingMode::Standard => CastlingRights::rights_for_square(sq),
+            CastlingMode::Chess960 => {
+                let mut rights = 0u8;
+                for &(color, back_rank) in &[(Color::White, 0u8), (Color::Black, 7u8)] {
+                    if sq.rank() != back_rank {
+                        continue;
+                    }
+                    let (kingside, queenside) = match color {
+                        Color::White => (CastlingRights::WHITE_KINGSIDE, CastlingRights::WHITE_QUEENSIDE),
+                        Color::Black => (CastlingRights::BLACK_KINGSIDE, CastlingRights::BLACK_QUEENSIDE),
+                    };
+                    if sq.file() == self.castling_king_files[color.index()] {
+                        rights |= kingside | queenside;
+                    }
+                    if sq.file() == self.castling_rook_files[color.index()][0] {
+                        rights |= kingside;
+                    }
+                    if sq.file() == self.castling_rook_files[color.index()][1] {
+                        rights |= queenside;
+                    }
+                }
+                rights
+            }
+        }
+    }
+
+    /// The castling rook's starting square and destination square for the
+    /// move `from -> to`, given which side (kingside if `to`'s file is
+    /// the g-file) it castles to. Shared by `make_move`/`unmake_move` so
+    /// both apply the exact same Standard/Chess960 rule.
+    fn castling_rook_squares(&self, us: Color, from: Square, to: Square) -> (Square, Square) {
+        let back_rank = from.rank();
+        // The king always lands on the g-file (kingside) or c-file
+        // (queenside), so `to`'s file identifies the side even in the
+        // Chess960 edge case where the king already starts there.
+        let kingside = to.file() == 6;
+        let rook_to_file = if kingside { 5 } else { 3 };
+        let rook_from = match self.castling_mode {
+            CastlingMode::Standard => {
+                if kingside {
+                    Square::new(from.0 + 3)
+                } else {
+                    Square::new(from.0 - 4)
+                }
+            }
+            CastlingMode::Chess960 => {
+                let side = if kingside { 0 } else { 1 };
+                Square::from_file_rank(self.castling_rook_files[us.index()][side], back_rank)
+            }
+        };
+        (rook_from, Square::from_file_rank(rook_to_file, back_rank))
+    }
+
+    /// Our own pieces pinned to our king by an aligned enemy slider
+    /// (bishop/rook/queen): exactly one of our pieces sits on the
+    /// [`MoveGen::between`] segment connecting the king to that slider.
+    /// A pinned piece may only move along [`MoveGen::line`] through the
+    /// king and the pinner without exposing it.
+    pub fn pinned(&self, color: Color) -> Bitboard {
+        use crate::engine::movegen::MoveGen;
+
+        let king_sq = self.king_square(color);
+        let us = self.color(color);
+        let them = self.color(color.opposite());
+        let occupied = self.occupied();
+
+        let bishop_pinners = (self.pieces(PieceType::Bishop) | self.pieces(PieceType::Queen)) & them;
+        let rook_pinners = (self.pieces(PieceType::Rook) | self.pieces(PieceType::Queen)) & them;
+
+        let mut pinned = Bitboard::EMPTY;
+        for pinner_sq in (MoveGen::bishop_attacks(king_sq, Bitboard::EMPTY) & bishop_pinners).iter() {
+            let blockers = MoveGen::between(king_sq, Square(pinner_sq)) & occupied;
+            if blockers.count() == 1 && (blockers & us).is_not_empty() {
+                pinned |= blockers;
+            }
+        }
+        for pinner_sq in (MoveGen::rook_attacks(king_sq, Bitboard::EMPTY) & rook_pinners).iter() {
+            let blockers = MoveGen::between(king_sq, Square(pinner_sq)) & occupied;
+            if blockers.count() == 1 && (blockers & us).is_not_empty() {
+                pinned |= blockers;
+            }
+        }
+        pinned
+    }
+
+    /// Make a move on the board, mutating it in place, and return the
+    /// [`UndoInfo`] needed to reverse it with [`Board::unmake_move`], or
+    /// `None` if the move left the mover's own king in check (in which
+    /// case the board is restored before returning). Replaces the old
+    /// clone-the-whole-board-per-node approach: `perft` and search now
+    /// push/pop a single `Board` instead of allocating one per move.
+    ///
+    /// A full post-move `is_attacked` scan is only needed when the move
+    /// could plausibly expose the king: the king itself moving, an en
+    /// passant capture (the one case that can uncover a same-rank pin no
+    /// per-piece pin check catches), a pinned piece moving, a move that
+    /// relocates the duck (its old square may have been the only blocker
+    /// on a slider's line to the king), or any move made while already
+    /// in check. Every other move is provably still
+    /// legal, since `pinned` already proves nothing else can discover a
+    /// check — turning most calls into a couple of bitboard intersections
+    /// instead of a board-wide attack sweep.
+    pub fn make_move(&mut self, mv: Move) -> Option<UndoInfo> {
+        let from = mv.from();
+        let piece = self.piece_at[from.index()]?;
+        let us = self.side_to_move;
+        let them = us.opposite();
+
+        let needs_check_scan = piece.piece_type == PieceType::King
+            || mv.is_en_passant()
+            || mv.duck_to().is_some()
+            || self.checkers.is_not_empty()
+            || self.pinned(us).contains(from.0);
+
+        let undo = self.make_move_unchecked(mv);
+
+        if needs_check_scan && self.is_attacked(self.king_square(us), them) {
+            self.unmake_move(mv, undo);
+            return None;
+        }
+
+        Some(undo)
+    }
+
+    /// Make a move on the board unconditionally, without checking whether
+    /// it leaves the mover's own king in check. Callers that only have
+    /// pseudo-legal moves (e.g. movegen's check/pin detection) can make,
+    /// probe with [`Board::is_attacked`], and [`Board::unmake_move`]
+    /// themselves instead of paying for [`Board::make_move`]'s built-in
+    /// legality check and auto-revert.
+    pub fn make_move_unchecked(&mut self, mv: Move) -> UndoInfo {
         let keys = ZobristKeys::instance();
         let from = mv.from();
         let to = mv.to();
-        let piece = match self.piece_at[from.index()] {
-            Some(p) => p,
-            None => return false,
-        };
+        let piece = self.piece_at[from.index()].expect("make_move_unchecked: no piece on from-square");
         let us = self.side_to_move;
         let them = us.opposite();
 
+        let mut undo = UndoInfo {
+            moved_piece: piece,
+            captured: None,
+            castling: self.castling,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            duck: self.duck,
+            hash: self.hash,
+            pawn_hash: self.pawn_hash,
+            checkers: self.checkers,
+        };
+
         // Save en passant state before clearing
         if let Some(ep) = self.en_passant {
             self.hash ^= keys.en_passant(ep);
@@ -704,13 +1231,16 @@ impl Board {
 
         // Handle castling rights changes
         let old_castling = self.castling;
-        self.castling.remove(CastlingRights::rights_for_square(from));
-        self.castling.remove(CastlingRights::rights_for_square(to));
+        self.castling.remove(self.castling_rights_for_square(from));
+        self.castling.remove(self.castling_rights_for_square(to));
         self.hash ^= keys.castling(old_castling);
         self.hash ^= keys.castling(self.castling);
 
         // Remove piece from source
         self.hash ^= keys.piece(piece, from);
+        if Self::is_pawn_hash_piece(piece.piece_type) {
+            self.pawn_hash ^= keys.piece(piece, from);
+        }
         self.remove_piece(from);
 
         // Handle captures
@@ -721,7 +1251,11 @@ impl Board {
                 to
             };
             if let Some(captured) = self.piece_at[capture_sq.index()] {
+                undo.captured = Some((captured, capture_sq));
                 self.hash ^= keys.piece(captured, capture_sq);
+                if Self::is_pawn_hash_piece(captured.piece_type) {
+                    self.pawn_hash ^= keys.piece(captured, capture_sq);
+                }
                 self.remove_piece(capture_sq);
             }
             self.halfmove_clock = 0;
@@ -731,15 +1265,9 @@ impl Board {
             self.halfmove_clock += 1;
         }
 
-        // Handle castling
+        // Handle castling (the rook itself never touches the pawn hash)
         if mv.is_castling() {
-            let (rook_from, rook_to) = if to.file() > from.file() {
-                // Kingside
-                (Square::new(from.0 + 3), Square::new(from.0 + 1))
-            } else {
-                // Queenside
-                (Square::new(from.0 - 4), Square::new(from.0 - 1))
-            };
+            let (rook_from, rook_to) = self.castling_rook_squares(us, from, to);
             if let Some(rook) = self.piece_at[rook_from.index()] {
                 self.hash ^= keys.piece(rook, rook_from);
                 self.remove_piece(rook_from);
@@ -755,6 +1283,9 @@ impl Board {
             piece
         };
         self.hash ^= keys.piece(placed_piece, to);
+        if Self::is_pawn_hash_piece(placed_piece.piece_type) {
+            self.pawn_hash ^= keys.piece(placed_piece, to);
+        }
         self.put_piece(placed_piece, to);
 
         // Handle double pawn push (set en passant)
@@ -767,6 +1298,16 @@ impl Board {
             }
         }
 
+        // DuckChess pairs every move with a duck relocation; apply it if
+        // `mv` carries one. XOR out the old duck key and XOR in the new
+        // one regardless of whether it actually moved, so an unchanged
+        // duck square is a no-op rather than a branch.
+        self.hash ^= keys.duck(self.duck);
+        if let Some(duck_to) = mv.duck_to() {
+            self.duck = Some(duck_to);
+        }
+        self.hash ^= keys.duck(self.duck);
+
         // Switch side to move
         self.side_to_move = them;
         self.hash ^= keys.side_to_move();
@@ -779,12 +1320,49 @@ impl Board {
         // Update checkers
         self.update_checkers();
 
-        // Check if the move was legal (king not in check)
-        if self.is_attacked(self.king_square(us), them) {
-            return false;
+        undo
+    }
+
+    /// Reverse a move previously applied by [`Board::make_move`], using
+    /// the [`UndoInfo`] it returned. `mv` must be the same move that
+    /// produced `undo`, and no other move may have been made on this
+    /// board in between.
+    pub fn unmake_move(&mut self, mv: Move, undo: UndoInfo) {
+        let from = mv.from();
+        let to = mv.to();
+
+        self.side_to_move = self.side_to_move.opposite();
+        let us = self.side_to_move;
+
+        // Lift whatever ended up on the destination square (the moved
+        // piece, or its promoted form).
+        self.remove_piece(to);
+
+        // Undo the rook hop of a castling move.
+        if mv.is_castling() {
+            let (rook_from, rook_to) = self.castling_rook_squares(us, from, to);
+            if let Some(rook) = self.piece_at[rook_to.index()] {
+                self.remove_piece(rook_to);
+                self.put_piece(rook, rook_from);
+            }
         }
 
-        true
+        // Restore whatever was captured (on `to`, or behind it for en passant).
+        if let Some((captured, capture_sq)) = undo.captured {
+            self.put_piece(captured, capture_sq);
+        }
+
+        // Restore the moved piece at its origin square (undoing any promotion).
+        self.put_piece(undo.moved_piece, from);
+
+        self.castling = undo.castling;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.duck = undo.duck;
+        self.hash = undo.hash;
+        self.pawn_hash = undo.pawn_hash;
+        self.checkers = undo.checkers;
     }
 
     /// Check if the current position is a draw by insufficient material
@@ -820,10 +1398,230 @@ impl Board {
 
         false
     }
+
+    /// Adjudicate the position under `self.variant`, using `history` (the
+    /// Zobrist hash, including the duck's square, of every position seen
+    /// so far this game in chronological order) to detect threefold
+    /// repetition.
+    ///
+    /// Standard chess is decided the usual way: checkmate, stalemate, the
+    /// fifty-move rule, threefold repetition, and insufficient material.
+    /// Duck chess has no concept of check — the duck can wall a king in
+    /// with nothing resembling a mating net — so it's decided purely by
+    /// king capture, with a side to move that has no legal move (however
+    /// that came about) simply losing on the spot.
+    pub fn terminal_state(&self, history: &[u64]) -> TerminalState {
+        if self.pieces_of(PieceType::King, Color::White).is_empty() {
+            return TerminalState::Win(Color::Black);
+        }
+        if self.pieces_of(PieceType::King, Color::Black).is_empty() {
+            return TerminalState::Win(Color::White);
+        }
+
+        if self.halfmove_clock >= 100 {
+            return TerminalState::Draw;
+        }
+        if history.iter().filter(|&&h| h == self.hash).count() >= 3 {
+            return TerminalState::Draw;
+        }
+
+        use crate::engine::movegen::MoveGen;
+        let has_legal_move = !MoveGen::generate_legal_moves(self).is_empty();
+
+        match self.variant {
+            Variant::DuckChess => {
+                if has_legal_move {
+                    TerminalState::Ongoing
+                } else {
+                    TerminalState::Win(self.side_to_move.opposite())
+                }
+            }
+            Variant::Standard => {
+                if !has_legal_move {
+                    if self.is_check() {
+                        TerminalState::Win(self.side_to_move.opposite())
+                    } else {
+                        TerminalState::Draw
+                    }
+                } else if self.is_insufficient_material() {
+                    TerminalState::Draw
+                } else {
+                    TerminalState::Ongoing
+                }
+            }
+        }
+    }
+}
+
+/// Builder for assembling a [`Board`] programmatically, one piece of state
+/// at a time, instead of poking its fields directly. Mirrors the "seer"
+/// engine's `ChessBoardBuilder`: each setter consumes and returns `self` so
+/// calls can be chained, and [`BoardBuilder::build`] is the single place
+/// that derives the hashes, checkers, and validity of the result.
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    /// Start from an empty board: no pieces, White to move, no castling
+    /// rights, no en passant square.
+    pub fn new() -> Self {
+        BoardBuilder {
+            board: Board::empty(),
+        }
+    }
+
+    /// Place a piece on a square.
+    pub fn piece(mut self, piece: Piece, sq: Square) -> Self {
+        self.board.put_piece(piece, sq);
+        self
+    }
+
+    /// Remove whatever piece (if any) occupies a square.
+    pub fn remove(mut self, sq: Square) -> Self {
+        self.board.remove_piece(sq);
+        self
+    }
+
+    /// Set the side to move.
+    pub fn side_to_move(mut self, color: Color) -> Self {
+        self.board.side_to_move = color;
+        self
+    }
+
+    /// Set the castling rights.
+    pub fn castling(mut self, castling: CastlingRights) -> Self {
+        self.board.castling = castling;
+        self
+    }
+
+    /// Set the castling mode along with the king/rook starting files it
+    /// governs. Only meaningful under `CastlingMode::Chess960`; standard
+    /// games can leave these at their `Board::empty()` defaults.
+    pub fn castling_mode(
+        mut self,
+        mode: CastlingMode,
+        king_files: [u8; 2],
+        rook_files: [[u8; 2]; 2],
+    ) -> Self {
+        self.board.castling_mode = mode;
+        self.board.castling_king_files = king_files;
+        self.board.castling_rook_files = rook_files;
+        self
+    }
+
+    /// Set the en passant target square.
+    pub fn en_passant(mut self, ep: Option<Square>) -> Self {
+        self.board.en_passant = ep;
+        self
+    }
+
+    /// Set the halfmove clock (for the 50-move rule).
+    pub fn halfmove_clock(mut self, halfmove_clock: u8) -> Self {
+        self.board.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    /// Set the fullmove number.
+    pub fn fullmove_number(mut self, fullmove_number: u16) -> Self {
+        self.board.fullmove_number = fullmove_number;
+        self
+    }
+
+    /// Place the duck.
+    pub fn duck(mut self, duck: Option<Square>) -> Self {
+        self.board.duck = duck;
+        self
+    }
+
+    /// Set which game-termination rules the board is adjudicated under.
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.board.variant = variant;
+        self
+    }
+
+    /// Read back a king's current square. Useful mid-build for callers
+    /// (such as Shredder-FEN parsing) that need to derive further state,
+    /// like a Chess960 castling file, from pieces already placed.
+    pub fn king_square(&self, color: Color) -> Square {
+        self.board.king_square(color)
+    }
+
+    /// Finish the build: calculate the hashes, find the checkers, and
+    /// validate the resulting position.
+    pub fn build(mut self) -> Result<Board, InvalidError> {
+        self.board.hash = self.board.calculate_hash();
+        self.board.pawn_hash = self.board.calculate_pawn_hash();
+        self.board.update_checkers();
+        self.board.is_valid()?;
+        Ok(self.board)
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Board {
+    /// Render the board with Unicode piece glyphs and alternating ANSI
+    /// background colors (warm for light squares, cool for dark), with
+    /// the duck's square called out by its own glyph/highlight. Pass
+    /// `flipped = true` to show the board from Black's perspective.
+    /// Terminal-only: unlike `to_fen`/`Display`, this is for human eyes,
+    /// not serialization.
+    pub fn render_pretty(&self, flipped: bool) -> String {
+        use std::fmt::Write as _;
+
+        const LIGHT_BG: &str = "\x1b[48;5;180m";
+        const DARK_BG: &str = "\x1b[48;5;94m";
+        const DUCK_BG: &str = "\x1b[48;5;220m";
+        const RESET: &str = "\x1b[0m";
+
+        let ranks: Vec<u8> = if flipped { (0..8).collect() } else { (0..8).rev().collect() };
+        let files: Vec<u8> = if flipped { (0..8).rev().collect() } else { (0..8).collect() };
+
+        let mut out = String::new();
+        for rank in ranks {
+            let _ = write!(out, " {} ", rank + 1);
+            for &file in &files {
+                let sq = Square::from_file_rank(file, rank);
+                let is_duck = self.duck == Some(sq);
+                let light = (Bitboard::LIGHT_SQUARES & Bitboard::from_square(sq.0)).is_not_empty();
+                let bg = if is_duck {
+                    DUCK_BG
+                } else if light {
+                    LIGHT_BG
+                } else {
+                    DARK_BG
+                };
+                let glyph = if is_duck {
+                    '◉'
+                } else {
+                    self.piece_at[sq.index()].map_or(' ', Piece::to_unicode)
+                };
+                let _ = write!(out, "{bg} {glyph} {RESET}");
+            }
+            out.push('\n');
+        }
+
+        out.push_str("   ");
+        for &file in &files {
+            let _ = write!(out, " {} ", (b'a' + file) as char);
+        }
+        out.push('\n');
+
+        out
+    }
 }
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.render_pretty(false));
+        }
+
         writeln!(f)?;
         for rank in (0..8).rev() {
             write!(f, "  {} ", rank + 1)?;
@@ -1,10 +1,16 @@
 //! Move representation
 //!
-//! Moves are encoded in a compact 16-bit format:
+//! Moves are encoded in a 32-bit format:
 //! - bits 0-5: from square (0-63)
 //! - bits 6-11: to square (0-63)
 //! - bits 12-13: promotion piece (0=Knight, 1=Bishop, 2=Rook, 3=Queen)
 //! - bits 14-15: move type (0=Normal, 1=Promotion, 2=En Passant, 3=Castling)
+//! - bits 16-21: duck relocation square (0-63), meaningful only if bit 22 is set
+//! - bit 22: the move carries a duck relocation (`Move::with_duck`)
+//!
+//! DuckChess pairs every piece move with a duck relocation, so a `Move`
+//! optionally carries both halves of a turn instead of needing a second
+//! value threaded alongside it everywhere a `Move` is passed around.
 
 use super::board::{PieceType, Square};
 use std::fmt;
@@ -19,34 +25,38 @@ pub enum MoveType {
     Castling = 3,
 }
 
-/// A chess move encoded in 16 bits
+/// A chess move, optionally paired with a duck relocation
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
-pub struct Move(u16);
+pub struct Move(u32);
 
 impl Move {
     pub const NULL: Move = Move(0);
 
-    const FROM_MASK: u16 = 0x003F;
-    const TO_MASK: u16 = 0x0FC0;
-    const TO_SHIFT: u16 = 6;
-    const PROMO_MASK: u16 = 0x3000;
-    const PROMO_SHIFT: u16 = 12;
-    const TYPE_MASK: u16 = 0xC000;
-    const TYPE_SHIFT: u16 = 14;
+    const FROM_MASK: u32 = 0x0000_003F;
+    const TO_MASK: u32 = 0x0000_0FC0;
+    const TO_SHIFT: u32 = 6;
+    const PROMO_MASK: u32 = 0x0000_3000;
+    const PROMO_SHIFT: u32 = 12;
+    const TYPE_MASK: u32 = 0x0000_C000;
+    const TYPE_SHIFT: u32 = 14;
 
     // Flag to indicate capture (stored in upper bit of promotion field when not promoting)
-    const CAPTURE_FLAG: u16 = 0x1000;
+    const CAPTURE_FLAG: u32 = 0x0000_1000;
+
+    const DUCK_MASK: u32 = 0x003F_0000;
+    const DUCK_SHIFT: u32 = 16;
+    const HAS_DUCK_FLAG: u32 = 0x0040_0000;
 
     /// Create a normal move
     #[inline]
     pub const fn new(from: Square, to: Square) -> Self {
-        Move((from.0 as u16) | ((to.0 as u16) << Self::TO_SHIFT))
+        Move((from.0 as u32) | ((to.0 as u32) << Self::TO_SHIFT))
     }
 
     /// Create a capture move
     #[inline]
     pub const fn new_capture(from: Square, to: Square) -> Self {
-        Move((from.0 as u16) | ((to.0 as u16) << Self::TO_SHIFT) | Self::CAPTURE_FLAG)
+        Move((from.0 as u32) | ((to.0 as u32) << Self::TO_SHIFT) | Self::CAPTURE_FLAG)
     }
 
     /// Create a promotion move
@@ -59,10 +69,10 @@ impl Move {
             PieceType::Queen => 3,
             _ => 0,
         };
-        let mut bits = (from.0 as u16)
-            | ((to.0 as u16) << Self::TO_SHIFT)
+        let mut bits = (from.0 as u32)
+            | ((to.0 as u32) << Self::TO_SHIFT)
             | (promo_bits << Self::PROMO_SHIFT)
-            | ((MoveType::Promotion as u16) << Self::TYPE_SHIFT);
+            | ((MoveType::Promotion as u32) << Self::TYPE_SHIFT);
         if is_capture {
             bits |= Self::CAPTURE_FLAG;
         }
@@ -73,9 +83,9 @@ impl Move {
     #[inline]
     pub const fn new_en_passant(from: Square, to: Square) -> Self {
         Move(
-            (from.0 as u16)
-                | ((to.0 as u16) << Self::TO_SHIFT)
-                | ((MoveType::EnPassant as u16) << Self::TYPE_SHIFT)
+            (from.0 as u32)
+                | ((to.0 as u32) << Self::TO_SHIFT)
+                | ((MoveType::EnPassant as u32) << Self::TYPE_SHIFT)
                 | Self::CAPTURE_FLAG, // En passant is always a capture
         )
     }
@@ -84,12 +94,31 @@ impl Move {
     #[inline]
     pub const fn new_castling(from: Square, to: Square) -> Self {
         Move(
-            (from.0 as u16)
-                | ((to.0 as u16) << Self::TO_SHIFT)
-                | ((MoveType::Castling as u16) << Self::TYPE_SHIFT),
+            (from.0 as u32)
+                | ((to.0 as u32) << Self::TO_SHIFT)
+                | ((MoveType::Castling as u32) << Self::TYPE_SHIFT),
         )
     }
 
+    /// Pair this move with a duck relocation to `duck_to`. DuckChess has
+    /// no legal turn without one (the duck must move to some empty
+    /// square), so this is applied once generation knows where the duck
+    /// is going.
+    #[inline]
+    pub const fn with_duck(self, duck_to: Square) -> Self {
+        Move((self.0 & !(Self::DUCK_MASK | Self::HAS_DUCK_FLAG)) | ((duck_to.0 as u32) << Self::DUCK_SHIFT) | Self::HAS_DUCK_FLAG)
+    }
+
+    /// Get the paired duck relocation, if this move carries one
+    #[inline]
+    pub const fn duck_to(self) -> Option<Square> {
+        if self.0 & Self::HAS_DUCK_FLAG == 0 {
+            None
+        } else {
+            Some(Square(((self.0 & Self::DUCK_MASK) >> Self::DUCK_SHIFT) as u8))
+        }
+    }
+
     /// Get the source square
     #[inline]
     pub const fn from(self) -> Square {
@@ -153,12 +182,19 @@ impl Move {
         })
     }
 
-    /// Get the raw 16-bit value
+    /// Get the raw encoded value
     #[inline]
-    pub const fn raw(self) -> u16 {
+    pub const fn raw(self) -> u32 {
         self.0
     }
 
+    /// Reconstruct a move from its raw encoding (e.g. when decoding a
+    /// packed transposition table entry)
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        Move(raw)
+    }
+
     /// Check if this is a null move
     #[inline]
     pub const fn is_null(self) -> bool {
@@ -15,6 +15,10 @@ pub struct ZobristKeys {
     castling: [u64; 16],
     /// Keys for en passant file
     en_passant: [u64; 8],
+    /// Keys for the duck's square, one per square
+    duck: [u64; 64],
+    /// Key used when the duck hasn't been placed yet
+    duck_unplaced: u64,
 }
 
 impl ZobristKeys {
@@ -47,11 +51,19 @@ impl ZobristKeys {
             *key = rng.next();
         }
 
+        let mut duck = [0u64; 64];
+        for key in duck.iter_mut() {
+            *key = rng.next();
+        }
+        let duck_unplaced = rng.next();
+
         ZobristKeys {
             pieces,
             side,
             castling,
             en_passant,
+            duck,
+            duck_unplaced,
         }
     }
 
@@ -79,6 +91,16 @@ impl ZobristKeys {
     pub fn en_passant(&self, sq: Square) -> u64 {
         self.en_passant[sq.file() as usize]
     }
+
+    /// Get the key for the duck's current square, or the "not yet placed"
+    /// key if it hasn't been put on the board.
+    #[inline]
+    pub fn duck(&self, sq: Option<Square>) -> u64 {
+        match sq {
+            Some(sq) => self.duck[sq.index()],
+            None => self.duck_unplaced,
+        }
+    }
 }
 
 /// Simple PRNG for generating Zobrist keys
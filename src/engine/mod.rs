@@ -9,10 +9,15 @@
 pub mod movegen;
 pub mod nnue;
 pub mod search;
+/// SIMD-accelerated inner loops for [`nnue`]; an implementation detail of
+/// that module rather than a piece of the engine's own public surface.
+mod simd;
+pub mod tablebase;
 pub mod tt;
 
-pub use movegen::MoveGen;
-pub use nnue::{evaluate, evaluator, NNUEAccumulator, NNUEEvaluator, NNUENetwork};
-pub use search::{SearchLimits, SearchStats, Searcher, INFINITY, MATE_SCORE};
+pub use movegen::{GenType, MoveGen};
+pub use nnue::{evaluate, evaluator, NNUEAccumulator, NNUEEvaluator, NNUELoadError, NNUENetwork};
+pub use search::{SearchLimits, SearchOptions, SearchStats, Searcher, INFINITY, MATE_SCORE};
+pub use tablebase::{Tablebase, Wdl};
 pub use tt::{TTEntry, TTFlag, TranspositionTable};
 
@@ -2,13 +2,55 @@
 //!
 //! This module implements efficient move generation using:
 //! - Precomputed attack tables for knights and kings
-//! - Magic bitboards for sliding pieces (bishops, rooks, queens)
+//! - Magic bitboards for sliding pieces (bishops, rooks, queens), with a
+//!   BMI2 PEXT lookup used instead when the CPU supports it. The magic
+//!   numbers and attack tables themselves are searched and enumerated by
+//!   `build.rs`, not at process startup
 //! - Pawn move generation with promotions and en passant
 
 use crate::core::bitboard::Bitboard;
-use crate::core::board::{Board, Color, PieceType, Square};
+use crate::core::board::{Board, CastlingMode, Color, PieceType, Square};
 use crate::core::moves::{Move, MoveList};
 
+/// Magic numbers, masks, and the full per-square attack tables, searched
+/// and enumerated once by `build.rs` instead of on every process startup.
+/// See `build.rs` for how these are derived.
+///
+/// No dummy/fallback copy of this module lives here: Cargo's build-script
+/// contract guarantees `build.rs` has already run and written
+/// `$OUT_DIR/magic_tables.rs` before this crate's own compilation starts,
+/// so the `include!` below always has a real file to pull in. A fallback
+/// path would be dead code for a state that can't occur.
+mod generated_magics {
+    include!(concat!(env!("OUT_DIR"), "/magic_tables.rs"));
+}
+
+/// Staged move-generation target, mirroring Stockfish's `GenType`: each
+/// variant just selects a different target mask for the shared per-piece
+/// generators, so callers can ask for only captures (for quiescence) or
+/// only quiets (for the back half of a staged move-picker) instead of
+/// generating everything and filtering afterwards.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GenType {
+    /// Moves that capture an enemy piece (includes capturing promotions
+    /// and en passant).
+    Captures,
+    /// Moves to empty squares (includes non-capturing promotions).
+    Quiets,
+    /// Quiet moves that give check, via `MoveGen::generate_checks`. When
+    /// the side to move is in check, this yields the quiet evasions
+    /// instead.
+    QuietChecks,
+    /// Legal when the king is in check: capture the checker or block it
+    /// (or, under double check, move the king).
+    Evasions,
+    /// Every move, castling included (captures and quiets together).
+    NonEvasions,
+    /// The fully-filtered legal move list: everything above, picking the
+    /// right target mask for whether the side to move is in check.
+    Legal,
+}
+
 /// Magic bitboard entry for sliding piece move generation
 #[derive(Clone, Copy)]
 struct Magic {
@@ -29,6 +71,58 @@ impl Magic {
     }
 }
 
+/// Extract the occupancy bits selected by `mask` into a dense index, via
+/// the BMI2 `PEXT` instruction. Callers must only reach this when
+/// `MoveGen::pext_supported()` returned true for the current CPU.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "bmi2")]
+unsafe fn pext_index(occupied: u64, mask: u64) -> usize {
+    std::arch::x86_64::_pext_u64(occupied, mask) as usize
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+unsafe fn pext_index(_occupied: u64, _mask: u64) -> usize {
+    unreachable!("PEXT is only used when MoveGen::pext_supported() is true")
+}
+
+/// Per-square attack slice for the BMI2 PEXT path. Unlike `Magic`, no
+/// multiply/shift is needed to hash an occupancy into an index: `_pext_u64`
+/// extracts the masked bits directly into a dense `0..1 << mask.count_ones()`
+/// index, so the table is packed with no wasted slots and no magic number.
+#[derive(Clone, Copy)]
+struct PextTable {
+    mask: u64,
+    attacks: &'static [Bitboard],
+}
+
+impl PextTable {
+    const fn empty() -> Self {
+        PextTable { mask: 0, attacks: &[] }
+    }
+}
+
+/// xorshift64 PRNG used only to draw magic-number candidates for
+/// `MoveGen::find_magic`. Mirrors `zobrist::SimpleRng`; kept as a separate
+/// copy since the two live in unrelated modules and seed from unrelated
+/// constants.
+struct MagicRng {
+    state: u64,
+}
+
+impl MagicRng {
+    fn new(seed: u64) -> Self {
+        // xorshift64 cannot recover from a zero state.
+        MagicRng { state: if seed == 0 { 0x9E3779B9_7F4A7C15 } else { seed } }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
 /// Precomputed attack tables
 pub struct MoveGen {
     // Knight attacks for each square
@@ -45,6 +139,19 @@ pub struct MoveGen {
     rook_attacks: Box<[Bitboard]>,
     // Storage for bishop attacks
     bishop_attacks: Box<[Bitboard]>,
+    // Rook attacks indexed via BMI2 PEXT, used instead of `rook_magics`
+    // when `use_pext` is true
+    rook_pext: [PextTable; 64],
+    // Bishop attacks indexed via BMI2 PEXT, used instead of `bishop_magics`
+    // when `use_pext` is true
+    bishop_pext: [PextTable; 64],
+    // Storage backing `rook_pext`
+    rook_pext_attacks: Box<[Bitboard]>,
+    // Storage backing `bishop_pext`
+    bishop_pext_attacks: Box<[Bitboard]>,
+    // Whether this CPU supports BMI2 PEXT; decided once at init and used
+    // to pick between `rook_pext`/`bishop_pext` and the magic fallback
+    use_pext: bool,
     // Lines between squares (for pin detection)
     between: [[Bitboard; 64]; 64],
     // Lines through squares (for ray attacks)
@@ -70,6 +177,11 @@ impl MoveGen {
             bishop_magics: [Magic::empty(); 64],
             rook_attacks: vec![Bitboard::EMPTY; 102400].into_boxed_slice(),
             bishop_attacks: vec![Bitboard::EMPTY; 5248].into_boxed_slice(),
+            rook_pext: [PextTable::empty(); 64],
+            bishop_pext: [PextTable::empty(); 64],
+            rook_pext_attacks: vec![Bitboard::EMPTY; 102400].into_boxed_slice(),
+            bishop_pext_attacks: vec![Bitboard::EMPTY; 5248].into_boxed_slice(),
+            use_pext: Self::pext_supported(),
             between: [[Bitboard::EMPTY; 64]; 64],
             line: [[Bitboard::EMPTY; 64]; 64],
         };
@@ -78,10 +190,30 @@ impl MoveGen {
         mg.init_king_attacks();
         mg.init_pawn_attacks();
         mg.init_magics();
+        if mg.use_pext {
+            mg.init_pext();
+        }
         mg.init_between_and_line();
         mg
     }
 
+    /// Detect BMI2 support at runtime. PEXT is present on the BMI2
+    /// extension; AMD CPUs before Zen 3 advertise BMI2 but emulate PEXT in
+    /// microcode (one bit at a time), which is slower than the magic
+    /// multiply, but we follow the same feature-detection contract the
+    /// pleco engine uses and let the fallback exist for non-x86_64 targets
+    /// and machines that lack BMI2 at all.
+    fn pext_supported() -> bool {
+        #[cfg(target_arch = "x86_64")]
+        {
+            is_x86_feature_detected!("bmi2")
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            false
+        }
+    }
+
     fn init_knight_attacks(&mut self) {
         for sq in 0..64 {
             let bb = Bitboard::from_square(sq);
@@ -138,99 +270,161 @@ impl MoveGen {
         }
     }
 
+    /// Check whether `magic` is collision-free for the relevant-occupancy
+    /// `mask`: every occupancy subset must map to a slot that is either
+    /// unused or already holds the identical attack set. Returns one past
+    /// the highest index touched (so callers can see how much smaller than
+    /// `1 << mask.count_ones()` the table could be packed), or `None` on
+    /// the first genuine collision. `build.rs` has its own copy of this
+    /// check for the tables it searches at build time; this one backs
+    /// `find_magic`, kept here for tests that want to verify a magic by
+    /// hand without depending on the build script's output.
+    #[allow(dead_code)]
+    fn magic_is_valid(&self, sq: u8, magic: u64, is_rook: bool) -> Option<usize> {
+        let mask = if is_rook { self.rook_mask(sq) } else { self.bishop_mask(sq) };
+        let bits = mask.count_ones();
+        let size = 1usize << bits;
+
+        let mut table: Vec<Option<Bitboard>> = vec![None; size];
+        let mut max_index = 0;
+
+        for i in 0..size {
+            let occ = self.index_to_occupancy(i, mask);
+            let attacks = self.sliding_attacks(sq, occ, is_rook);
+            let idx = ((occ.wrapping_mul(magic)) >> (64 - bits)) as usize;
+            max_index = max_index.max(idx);
+
+            match table[idx] {
+                None => table[idx] = Some(attacks),
+                Some(existing) if existing == attacks => {}
+                Some(_) => return None,
+            }
+        }
+
+        Some(max_index + 1)
+    }
+
+    /// Search for a collision-free magic number for `sq`, in the style of
+    /// the seer engine's generated `magic::moves` module: draw sparse
+    /// candidates (the AND of three random draws tends to have few set
+    /// bits, which hashes occupancies well) and keep the first one that
+    /// `magic_is_valid` accepts. Returns the magic together with the
+    /// number of attack-table slots it actually uses, which is usually far
+    /// below the `1 << mask.count_ones()` upper bound the hardcoded tables
+    /// budget for.
+    #[allow(dead_code)]
+    fn find_magic(&self, sq: u8, is_rook: bool) -> (u64, usize) {
+        let mut rng = MagicRng::new(0x9E3779B9_7F4A7C15 ^ (sq as u64) << 1 ^ (is_rook as u64));
+
+        loop {
+            let magic = rng.next() & rng.next() & rng.next();
+
+            // Magics whose top byte barely varies with the mask hash
+            // poorly in practice; skip them before paying for the full
+            // collision scan.
+            let mask = if is_rook { self.rook_mask(sq) } else { self.bishop_mask(sq) };
+            if (mask.wrapping_mul(magic) >> 56).count_ones() < 6 {
+                continue;
+            }
+
+            if let Some(size) = self.magic_is_valid(sq, magic, is_rook) {
+                return (magic, size);
+            }
+        }
+    }
+
+    /// Wire up the magic-bitboard tables `build.rs` searched and
+    /// enumerated at build time: just copy its flattened `u64` attack
+    /// arrays into `self.rook_attacks`/`self.bishop_attacks` and point
+    /// each square's `Magic` at its slice, with no per-square search or
+    /// occupancy-subset enumeration left to do at startup.
     fn init_magics(&mut self) {
-        // Pre-computed magic numbers for rooks and bishops
-        // These are well-known magic numbers that work efficiently
-        const ROOK_MAGICS: [u64; 64] = [
-            0x0080001020400080, 0x0040001000200040, 0x0080081000200080, 0x0080040800100080,
-            0x0080020400080080, 0x0080010200040080, 0x0080008001000200, 0x0080002040800100,
-            0x0000800020400080, 0x0000400020005000, 0x0000801000200080, 0x0000800800100080,
-            0x0000800400080080, 0x0000800200040080, 0x0000800100020080, 0x0000800040800100,
-            0x0000208000400080, 0x0000404000201000, 0x0000808010002000, 0x0000808008001000,
-            0x0000808004000800, 0x0000808002000400, 0x0000010100020004, 0x0000020000408104,
-            0x0000208080004000, 0x0000200040005000, 0x0000100080200080, 0x0000080080100080,
-            0x0000040080080080, 0x0000020080040080, 0x0000010080800200, 0x0000800080004100,
-            0x0000204000800080, 0x0000200040401000, 0x0000100080802000, 0x0000080080801000,
-            0x0000040080800800, 0x0000020080800400, 0x0000020001010004, 0x0000800040800100,
-            0x0000204000808000, 0x0000200040008080, 0x0000100020008080, 0x0000080010008080,
-            0x0000040008008080, 0x0000020004008080, 0x0000010002008080, 0x0000004081020004,
-            0x0000204000800080, 0x0000200040008080, 0x0000100020008080, 0x0000080010008080,
-            0x0000040008008080, 0x0000020004008080, 0x0000800100020080, 0x0000800041000080,
-            0x00FFFCDDFCED714A, 0x007FFCDDFCED714A, 0x003FFFCDFFD88096, 0x0000040810002101,
-            0x0001000204080011, 0x0001000204000801, 0x0001000082000401, 0x0001FFFAABFAD1A2,
-        ];
+        for sq in 0..64usize {
+            let rook_mask = generated_magics::ROOK_MASKS[sq];
+            let rook_offset = generated_magics::ROOK_OFFSETS[sq];
+            let rook_size = 1usize << rook_mask.count_ones();
+
+            self.rook_attacks[rook_offset..rook_offset + rook_size]
+                .copy_from_slice(
+                    &generated_magics::ROOK_ATTACK_TABLE[rook_offset..rook_offset + rook_size]
+                        .iter()
+                        .map(|&bits| Bitboard(bits))
+                        .collect::<Vec<_>>(),
+                );
+            self.rook_magics[sq] = Magic {
+                mask: rook_mask,
+                magic: generated_magics::ROOK_MAGICS[sq],
+                attacks: unsafe {
+                    std::slice::from_raw_parts(self.rook_attacks.as_ptr().add(rook_offset), rook_size)
+                },
+                shift: generated_magics::ROOK_SHIFTS[sq],
+            };
 
-        const BISHOP_MAGICS: [u64; 64] = [
-            0x0002020202020200, 0x0002020202020000, 0x0004010202000000, 0x0004040080000000,
-            0x0001104000000000, 0x0000821040000000, 0x0000410410400000, 0x0000104104104000,
-            0x0000040404040400, 0x0000020202020200, 0x0000040102020000, 0x0000040400800000,
-            0x0000011040000000, 0x0000008210400000, 0x0000004104104000, 0x0000002082082000,
-            0x0004000808080800, 0x0002000404040400, 0x0001000202020200, 0x0000800802004000,
-            0x0000800400A00000, 0x0000200100884000, 0x0000400082082000, 0x0000200041041000,
-            0x0002080010101000, 0x0001040008080800, 0x0000208004010400, 0x0000404004010200,
-            0x0000840000802000, 0x0000404002011000, 0x0000808001041000, 0x0000404000820800,
-            0x0001041000202000, 0x0000820800101000, 0x0000104400080800, 0x0000020080080080,
-            0x0000404040040100, 0x0000808100020100, 0x0001010100020800, 0x0000808080010400,
-            0x0000820820004000, 0x0000410410002000, 0x0000082088001000, 0x0000002011000800,
-            0x0000080100400400, 0x0001010101000200, 0x0002020202000400, 0x0001010101000200,
-            0x0000410410400000, 0x0000208208200000, 0x0000002084100000, 0x0000000020880000,
-            0x0000001002020000, 0x0000040408020000, 0x0004040404040000, 0x0002020202020000,
-            0x0000104104104000, 0x0000002082082000, 0x0000000020841000, 0x0000000000208800,
-            0x0000000010020200, 0x0000000404080200, 0x0000040404040400, 0x0002020202020200,
-        ];
+            let bishop_mask = generated_magics::BISHOP_MASKS[sq];
+            let bishop_offset = generated_magics::BISHOP_OFFSETS[sq];
+            let bishop_size = 1usize << bishop_mask.count_ones();
+
+            self.bishop_attacks[bishop_offset..bishop_offset + bishop_size]
+                .copy_from_slice(
+                    &generated_magics::BISHOP_ATTACK_TABLE[bishop_offset..bishop_offset + bishop_size]
+                        .iter()
+                        .map(|&bits| Bitboard(bits))
+                        .collect::<Vec<_>>(),
+                );
+            self.bishop_magics[sq] = Magic {
+                mask: bishop_mask,
+                magic: generated_magics::BISHOP_MAGICS[sq],
+                attacks: unsafe {
+                    std::slice::from_raw_parts(self.bishop_attacks.as_ptr().add(bishop_offset), bishop_size)
+                },
+                shift: generated_magics::BISHOP_SHIFTS[sq],
+            };
+        }
+    }
 
+    /// Fill the PEXT attack tables. Unlike `init_magics`, no magic number
+    /// or collision search is needed: `_pext_u64(occupied, mask)` and
+    /// `index_to_occupancy(i, mask)` are exact inverses of each other, so
+    /// occupancy subset `i` is simply stored at index `i`.
+    fn init_pext(&mut self) {
         let mut rook_offset = 0;
         let mut bishop_offset = 0;
 
         for sq in 0..64 {
-            // Initialize rook magics
             let rook_mask = self.rook_mask(sq);
-            let rook_bits = rook_mask.count_ones();
-            let rook_size = 1 << rook_bits;
+            let rook_size = 1usize << rook_mask.count_ones();
 
-            self.rook_magics[sq as usize] = Magic {
+            for i in 0..rook_size {
+                let occ = self.index_to_occupancy(i, rook_mask);
+                self.rook_pext_attacks[rook_offset + i] = self.sliding_attacks(sq, occ, true);
+            }
+            self.rook_pext[sq as usize] = PextTable {
                 mask: rook_mask,
-                magic: ROOK_MAGICS[sq as usize],
                 attacks: unsafe {
                     std::slice::from_raw_parts(
-                        self.rook_attacks.as_ptr().add(rook_offset),
+                        self.rook_pext_attacks.as_ptr().add(rook_offset),
                         rook_size,
                     )
                 },
-                shift: 64 - rook_bits,
             };
-
-            // Fill rook attack table
-            for i in 0..rook_size {
-                let occ = self.index_to_occupancy(i, rook_mask);
-                let idx = ((occ.wrapping_mul(ROOK_MAGICS[sq as usize])) >> (64 - rook_bits)) as usize;
-                self.rook_attacks[rook_offset + idx] = self.sliding_attacks(sq, occ, true);
-            }
             rook_offset += rook_size;
 
-            // Initialize bishop magics
             let bishop_mask = self.bishop_mask(sq);
-            let bishop_bits = bishop_mask.count_ones();
-            let bishop_size = 1 << bishop_bits;
+            let bishop_size = 1usize << bishop_mask.count_ones();
 
-            self.bishop_magics[sq as usize] = Magic {
+            for i in 0..bishop_size {
+                let occ = self.index_to_occupancy(i, bishop_mask);
+                self.bishop_pext_attacks[bishop_offset + i] = self.sliding_attacks(sq, occ, false);
+            }
+            self.bishop_pext[sq as usize] = PextTable {
                 mask: bishop_mask,
-                magic: BISHOP_MAGICS[sq as usize],
                 attacks: unsafe {
                     std::slice::from_raw_parts(
-                        self.bishop_attacks.as_ptr().add(bishop_offset),
+                        self.bishop_pext_attacks.as_ptr().add(bishop_offset),
                         bishop_size,
                     )
                 },
-                shift: 64 - bishop_bits,
             };
-
-            // Fill bishop attack table
-            for i in 0..bishop_size {
-                let occ = self.index_to_occupancy(i, bishop_mask);
-                let idx = ((occ.wrapping_mul(BISHOP_MAGICS[sq as usize])) >> (64 - bishop_bits)) as usize;
-                self.bishop_attacks[bishop_offset + idx] = self.sliding_attacks(sq, occ, false);
-            }
             bishop_offset += bishop_size;
         }
     }
@@ -425,18 +619,28 @@ impl MoveGen {
     #[inline]
     pub fn rook_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
         let mg = Self::instance();
-        let magic = &mg.rook_magics[sq.index()];
-        let idx = (((occupied.0 & magic.mask).wrapping_mul(magic.magic)) >> magic.shift) as usize;
-        magic.attacks[idx]
+        if mg.use_pext {
+            let table = &mg.rook_pext[sq.index()];
+            table.attacks[unsafe { pext_index(occupied.0, table.mask) }]
+        } else {
+            let magic = &mg.rook_magics[sq.index()];
+            let idx = (((occupied.0 & magic.mask).wrapping_mul(magic.magic)) >> magic.shift) as usize;
+            magic.attacks[idx]
+        }
     }
 
     /// Get bishop attacks for a square with given occupancy
     #[inline]
     pub fn bishop_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
         let mg = Self::instance();
-        let magic = &mg.bishop_magics[sq.index()];
-        let idx = (((occupied.0 & magic.mask).wrapping_mul(magic.magic)) >> magic.shift) as usize;
-        magic.attacks[idx]
+        if mg.use_pext {
+            let table = &mg.bishop_pext[sq.index()];
+            table.attacks[unsafe { pext_index(occupied.0, table.mask) }]
+        } else {
+            let magic = &mg.bishop_magics[sq.index()];
+            let idx = (((occupied.0 & magic.mask).wrapping_mul(magic.magic)) >> magic.shift) as usize;
+            magic.attacks[idx]
+        }
     }
 
     /// Get queen attacks for a square with given occupancy
@@ -459,12 +663,99 @@ impl MoveGen {
 
     /// Generate all legal moves for a position
     pub fn generate_legal_moves(board: &Board) -> MoveList {
+        Self::generate(board, GenType::Legal)
+    }
+
+    /// Enumerate duck-relocation moves: placing the duck on any empty
+    /// square, i.e. every square except those occupied by a piece and the
+    /// duck's own current square (which `Board::occupied` already
+    /// includes). A full DuckChess turn pairs one of these with a normal
+    /// chess move; composing the pair is left to the caller. If the duck
+    /// hasn't been placed yet, `from` is reported as the destination
+    /// square itself (a move can never have `from == to` otherwise), so
+    /// callers can distinguish an initial placement from a relocation.
+    pub fn generate_duck_moves(board: &Board) -> MoveList {
+        let mut moves = MoveList::new();
+        let empty = !board.occupied();
+
+        for to in empty.iter() {
+            let from = board.duck.unwrap_or(Square(to));
+            moves.push(Move::new(from, Square(to)));
+        }
+
+        moves
+    }
+
+    /// Generate every legal DuckChess move: a piece move paired with a
+    /// duck relocation to each square left empty afterwards. DuckChess has
+    /// no check or checkmate (a game ends when a king is actually
+    /// captured), so unlike `generate_legal_moves` this neither confines a
+    /// pinned piece to its pin ray nor forbids the king from stepping into
+    /// an attacked square, and castling through an attacked square is
+    /// legal too — only the orthodox king-capture-leaves-no-king rule
+    /// still applies, and it falls out of move generation for free since
+    /// an enemy king is just another capturable piece.
+    pub fn generate_duck_chess_moves(board: &Board) -> MoveList {
+        let mut moves = MoveList::new();
+        let piece_moves = Self::generate_pseudo_legal_no_check(board);
+
+        for mv in piece_moves.iter() {
+            let mut after = board.clone();
+            after.make_move_unchecked(*mv);
+            for to in (!after.occupied()).iter() {
+                moves.push(mv.with_duck(Square(to)));
+            }
+        }
+
+        moves
+    }
+
+    /// Piece-move generation with no pin, check-evasion, or king-safety
+    /// filtering at all — the `generate()` machinery with `pinned` forced
+    /// empty and an unrestricted `target_mask`, backing
+    /// `generate_duck_chess_moves`.
+    fn generate_pseudo_legal_no_check(board: &Board) -> MoveList {
+        let mut moves = MoveList::new();
+        let us = board.side_to_move;
+        let our_pieces = board.color(us);
+        let their_pieces = board.color(us.opposite());
+        let occupied = board.occupied();
+        let duck = board.duck_bitboard();
+
+        let target_mask = Self::gen_selection_mask(GenType::Legal, our_pieces, their_pieces, occupied, duck);
+        let no_pins = Bitboard::EMPTY;
+        let no_pin_rays = [[Bitboard::EMPTY; 64]; 2];
+
+        Self::generate_pawn_moves(board, &mut moves, no_pins, &no_pin_rays, target_mask);
+        Self::generate_knight_moves(board, &mut moves, no_pins, target_mask);
+        Self::generate_bishop_moves(board, &mut moves, no_pins, &no_pin_rays, target_mask);
+        Self::generate_rook_moves(board, &mut moves, no_pins, &no_pin_rays, target_mask);
+        Self::generate_queen_moves(board, &mut moves, no_pins, &no_pin_rays, target_mask);
+        Self::generate_king_moves_with_safety(board, &mut moves, target_mask, false);
+        Self::generate_castling_moves_with_safety(board, &mut moves, false);
+
+        moves
+    }
+
+    /// Staged move generation: only the moves selected by `gen_type`, built
+    /// from the same pinned/check-evasion machinery `generate_legal_moves`
+    /// always ran, but applied to a narrower target mask so a caller that
+    /// only wants captures never pays to generate and discard quiets.
+    pub fn generate(board: &Board, gen_type: GenType) -> MoveList {
+        // Quiet checks out of check don't make sense (see `Evasions`
+        // instead), so only divert to the specialized generator when the
+        // side to move isn't already in check.
+        if gen_type == GenType::QuietChecks && board.checkers.is_empty() {
+            return Self::generate_checks(board);
+        }
+
         let mut moves = MoveList::new();
         let us = board.side_to_move;
         let them = us.opposite();
         let our_pieces = board.color(us);
         let their_pieces = board.color(them);
         let occupied = board.occupied();
+        let duck = board.duck_bitboard();
         let king_sq = board.king_square(us);
 
         // Calculate pinned pieces and check mask
@@ -473,7 +764,8 @@ impl MoveGen {
 
         // If double check, only king moves are legal
         if num_checkers > 1 {
-            Self::generate_king_moves(board, &mut moves);
+            let king_target_mask = Self::gen_selection_mask(gen_type, our_pieces, their_pieces, occupied, duck);
+            Self::generate_king_moves(board, &mut moves, king_target_mask);
             return moves;
         }
 
@@ -505,26 +797,195 @@ impl MoveGen {
             }
         }
 
-        // Calculate target squares (if in check, must block or capture checker)
-        let target_mask = if num_checkers == 1 {
+        // Squares that block or capture a single checker; ALL otherwise.
+        let check_mask = if num_checkers == 1 {
             let checker_sq = Square(checkers.lsb());
             Self::between(king_sq, checker_sq) | checkers
         } else {
             Bitboard::ALL
         };
 
+        // King moves aren't constrained by `check_mask` (blocking/capturing
+        // the checker is irrelevant to the king itself; it relies on the
+        // attacked-square test in `generate_king_moves` instead).
+        let king_target_mask = Self::gen_selection_mask(gen_type, our_pieces, their_pieces, occupied, duck);
+        let target_mask = king_target_mask & check_mask;
+
         // Generate moves for each piece type
         Self::generate_pawn_moves(board, &mut moves, pinned, &pin_rays, target_mask);
         Self::generate_knight_moves(board, &mut moves, pinned, target_mask);
         Self::generate_bishop_moves(board, &mut moves, pinned, &pin_rays, target_mask);
         Self::generate_rook_moves(board, &mut moves, pinned, &pin_rays, target_mask);
         Self::generate_queen_moves(board, &mut moves, pinned, &pin_rays, target_mask);
-        Self::generate_king_moves(board, &mut moves);
-        Self::generate_castling_moves(board, &mut moves);
+        Self::generate_king_moves(board, &mut moves, king_target_mask);
+
+        // Castling is neither a capture nor an evasion (it's illegal out of
+        // check anyway; `generate_castling_moves` enforces that itself).
+        if matches!(gen_type, GenType::Quiets | GenType::QuietChecks | GenType::NonEvasions | GenType::Legal) {
+            Self::generate_castling_moves(board, &mut moves);
+        }
 
         moves
     }
 
+    /// Specialized generator behind `GenType::QuietChecks`, following
+    /// Stockfish's `generate_piece_checks`: narrow the full quiet move list
+    /// down to the ones that deliver check, instead of generating
+    /// everything and filtering later.
+    ///
+    /// A quiet move gives a "direct" check when the piece lands on one of
+    /// the per-piece-type check squares precomputed from the enemy king's
+    /// square, or a "discovered" check when it moves one of our own pieces
+    /// off the line between the enemy king and one of our sliders that it
+    /// was the sole blocker for (the same single-blocker test `generate`
+    /// uses for pins, with the roles reversed: our sliders seeing through
+    /// our own blockers instead of their sliders pinning us). A move can
+    /// be both; since both conditions are evaluated together as one `||`
+    /// per move, it is still only ever pushed once.
+    pub fn generate_checks(board: &Board) -> MoveList {
+        let us = board.side_to_move;
+        let them = us.opposite();
+        let occupied = board.occupied();
+        let our_pieces = board.color(us);
+        let ksq = board.king_square(them);
+
+        let bishop_check_sq = Self::bishop_attacks(ksq, occupied);
+        let rook_check_sq = Self::rook_attacks(ksq, occupied);
+        let knight_check_sq = Self::knight_attacks(ksq);
+        let queen_check_sq = bishop_check_sq | rook_check_sq;
+        // The squares a `them` pawn would attack from `ksq` are exactly
+        // the squares one of our pawns attacks `ksq` from.
+        let pawn_check_sq = Self::pawn_attacks(ksq, them);
+
+        let our_bishop_sliders = (board.pieces(PieceType::Bishop) | board.pieces(PieceType::Queen)) & our_pieces;
+        let our_rook_sliders = (board.pieces(PieceType::Rook) | board.pieces(PieceType::Queen)) & our_pieces;
+
+        let mut dc_candidates = Bitboard::EMPTY;
+        let mut dc_line = [Bitboard::EMPTY; 64];
+
+        for (sliders, is_rook) in [(our_bishop_sliders, false), (our_rook_sliders, true)] {
+            let attackers = if is_rook {
+                Self::rook_attacks(ksq, Bitboard::EMPTY)
+            } else {
+                Self::bishop_attacks(ksq, Bitboard::EMPTY)
+            } & sliders;
+
+            for slider_sq in attackers.iter() {
+                let between = Self::between(ksq, Square(slider_sq));
+                let blockers = between & occupied;
+                if blockers.count() == 1 {
+                    let blocker_sq = blockers.lsb();
+                    dc_candidates.set(blocker_sq);
+                    dc_line[blocker_sq as usize] = Self::line(ksq, Square(slider_sq));
+                }
+            }
+        }
+
+        let quiets = Self::generate(board, GenType::Quiets);
+        let mut checks = MoveList::new();
+
+        for mv in quiets.iter() {
+            let from = mv.from();
+            let to = mv.to();
+            let piece_type = match board.piece_at[from.index()] {
+                Some(piece) => piece.piece_type,
+                None => continue,
+            };
+            let landing_piece = mv.promotion_piece().unwrap_or(piece_type);
+
+            let direct = match landing_piece {
+                PieceType::Pawn => pawn_check_sq.contains(to.0),
+                PieceType::Knight => knight_check_sq.contains(to.0),
+                PieceType::Bishop => bishop_check_sq.contains(to.0),
+                PieceType::Rook => rook_check_sq.contains(to.0),
+                PieceType::Queen => queen_check_sq.contains(to.0),
+                PieceType::King => false,
+            };
+
+            let discovered =
+                dc_candidates.contains(from.0) && !dc_line[from.index()].contains(to.0);
+
+            if direct || discovered {
+                checks.push(*mv);
+            }
+        }
+
+        checks
+    }
+
+    /// Count leaf nodes reachable from `board` after exactly `depth` plies
+    /// of legal play. Recurses by generating legal moves, applying each
+    /// with `Board::make_move`/`unmake_move` against a single mutable
+    /// board, and recursing at `depth - 1`; at `depth == 1` every move in
+    /// a legal move list is itself one leaf, so the count is returned
+    /// directly (bulk counting) instead of paying for a final make/unmake
+    /// layer. Used to validate `generate_legal_moves`/`make_move` against
+    /// known node counts for standard test positions.
+    pub fn perft(board: &Board, depth: u32) -> u64 {
+        let mut board = board.clone();
+        Self::perft_mut(&mut board, depth)
+    }
+
+    fn perft_mut(board: &mut Board, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = Self::generate_legal_moves(board);
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in moves.iter() {
+            if let Some(undo) = board.make_move(*mv) {
+                nodes += Self::perft_mut(board, depth - 1);
+                board.unmake_move(*mv, undo);
+            }
+        }
+        nodes
+    }
+
+    /// Like [`MoveGen::perft`], but reports the subtree count under each
+    /// root move individually instead of just the total, so a node-count
+    /// mismatch can be localized to a single root move ("perft divide").
+    pub fn perft_divide(board: &Board, depth: u32) -> Vec<(Move, u64)> {
+        let mut board = board.clone();
+        let moves = Self::generate_legal_moves(&board);
+        let mut divided = Vec::with_capacity(moves.len());
+
+        for mv in moves.iter() {
+            if let Some(undo) = board.make_move(*mv) {
+                let nodes = if depth > 1 { Self::perft_mut(&mut board, depth - 1) } else { 1 };
+                board.unmake_move(*mv, undo);
+                divided.push((*mv, nodes));
+            }
+        }
+
+        divided
+    }
+
+    /// The target-square mask a [`GenType`] selects on its own, before the
+    /// check-evasion mask is applied (king moves use this directly; other
+    /// pieces additionally AND it with the evasion mask). The duck belongs
+    /// to neither color and can never be landed on, so every branch masks
+    /// it out even though it isn't a member of `our_pieces`/`their_pieces`.
+    fn gen_selection_mask(
+        gen_type: GenType,
+        our_pieces: Bitboard,
+        their_pieces: Bitboard,
+        occupied: Bitboard,
+        duck: Bitboard,
+    ) -> Bitboard {
+        let base = match gen_type {
+            GenType::Captures => their_pieces,
+            GenType::Quiets | GenType::QuietChecks => !occupied,
+            GenType::Evasions => Bitboard::ALL,
+            GenType::NonEvasions | GenType::Legal => !our_pieces,
+        };
+        base & !duck
+    }
+
     fn generate_pawn_moves(
         board: &Board,
         moves: &mut MoveList,
@@ -769,7 +1230,21 @@ impl MoveGen {
         }
     }
 
-    fn generate_king_moves(board: &Board, moves: &mut MoveList) {
+    fn generate_king_moves(board: &Board, moves: &mut MoveList, target_mask: Bitboard) {
+        Self::generate_king_moves_with_safety(board, moves, target_mask, true);
+    }
+
+    /// King move generation with the attacked-square filter optional, so
+    /// `generate_duck_chess_moves` can reuse it: under DuckChess rules
+    /// there's no check, so the king may step into an attacked square (and
+    /// be captured there like any other piece) just as freely as it can
+    /// move anywhere else.
+    fn generate_king_moves_with_safety(
+        board: &Board,
+        moves: &mut MoveList,
+        target_mask: Bitboard,
+        filter_unsafe: bool,
+    ) {
         let us = board.side_to_move;
         let them = us.opposite();
         let king_sq = board.king_square(us);
@@ -777,14 +1252,17 @@ impl MoveGen {
         let their_pieces = board.color(them);
         let occupied = board.occupied();
 
-        let attacks = Self::king_attacks(king_sq) & !our_pieces;
+        let mut attacks = Self::king_attacks(king_sq) & !our_pieces & target_mask;
+        if filter_unsafe {
+            // Squares attacked with the king itself removed from the
+            // occupancy, so a slider's ray still marks the squares behind
+            // the king as unsafe (the king can't "outrun" a check along
+            // the checking ray).
+            let danger = Self::attacked_squares(board, them, occupied ^ king_sq.bitboard());
+            attacks &= !danger;
+        }
 
         for to in attacks.iter() {
-            // Check if destination is attacked
-            let after_occ = (occupied ^ king_sq.bitboard()) | Bitboard::from_square(to);
-            if Self::is_square_attacked_with_occ(board, Square(to), them, after_occ) {
-                continue;
-            }
             if their_pieces.contains(to) {
                 moves.push(Move::new_capture(king_sq, Square(to)));
             } else {
@@ -794,15 +1272,47 @@ impl MoveGen {
     }
 
     fn generate_castling_moves(board: &Board, moves: &mut MoveList) {
+        Self::generate_castling_moves_with_safety(board, moves, true);
+    }
+
+    /// Castling generation with the check/attacked-square rules optional,
+    /// so `generate_duck_chess_moves` can reuse it: DuckChess has no
+    /// check, so castling out of or through an attacked square is legal
+    /// (only the path still has to be physically clear).
+    fn generate_castling_moves_with_safety(board: &Board, moves: &mut MoveList, filter_unsafe: bool) {
         let us = board.side_to_move;
         let them = us.opposite();
         let occupied = board.occupied();
 
         // Can't castle out of check
-        if board.is_check() {
+        if filter_unsafe && board.is_check() {
             return;
         }
 
+        // Computed once and reused for both the kingside and queenside
+        // attempts below, instead of a fresh attack query per candidate
+        // square.
+        let danger = if filter_unsafe {
+            Self::attacked_squares(board, them, occupied)
+        } else {
+            Bitboard::EMPTY
+        };
+
+        match board.castling_mode {
+            CastlingMode::Standard => Self::generate_standard_castling(board, moves, filter_unsafe, danger, occupied, us),
+            CastlingMode::Chess960 => Self::generate_chess960_castling(board, moves, filter_unsafe, danger, occupied, us),
+        }
+    }
+
+    /// Fixed fast path for the classical e/a/h-file layout.
+    fn generate_standard_castling(
+        board: &Board,
+        moves: &mut MoveList,
+        filter_unsafe: bool,
+        danger: Bitboard,
+        occupied: Bitboard,
+        us: Color,
+    ) {
         let (king_sq, king_side_to, queen_side_to, king_path, queen_clear) = match us {
             Color::White => (
                 Square::E1,
@@ -824,9 +1334,7 @@ impl MoveGen {
         if board.castling.can_castle_kingside(us) {
             if (occupied & king_path).is_empty() {
                 let through_sq = Square::new(king_sq.0 + 1);
-                if !Self::is_square_attacked(board, through_sq, them) 
-                    && !Self::is_square_attacked(board, king_side_to, them) 
-                {
+                if !filter_unsafe || !danger.contains(through_sq.0) && !danger.contains(king_side_to.0) {
                     moves.push(Move::new_castling(king_sq, king_side_to));
                 }
             }
@@ -836,55 +1344,99 @@ impl MoveGen {
         if board.castling.can_castle_queenside(us) {
             if (occupied & queen_clear).is_empty() {
                 let through_sq = Square::new(king_sq.0 - 1);
-                if !Self::is_square_attacked(board, through_sq, them) 
-                    && !Self::is_square_attacked(board, queen_side_to, them) 
-                {
+                if !filter_unsafe || !danger.contains(through_sq.0) && !danger.contains(queen_side_to.0) {
                     moves.push(Move::new_castling(king_sq, queen_side_to));
                 }
             }
         }
     }
 
-    /// Check if a square is attacked by a given color
-    fn is_square_attacked(board: &Board, sq: Square, by_color: Color) -> bool {
-        board.is_attacked(sq, by_color)
-    }
+    /// Fischer Random castling: the king and rook may start on any file,
+    /// so both the empty-square test and the not-attacked test are
+    /// derived from their actual starting files instead of assuming the
+    /// classical layout. The empty-square test excludes the castling
+    /// king's and rook's own squares, since one of them may already sit
+    /// on a square the other needs to pass through.
+    fn generate_chess960_castling(
+        board: &Board,
+        moves: &mut MoveList,
+        filter_unsafe: bool,
+        danger: Bitboard,
+        occupied: Bitboard,
+        us: Color,
+    ) {
+        let back_rank = board.king_square(us).rank();
+        let king_from = board.king_square(us);
+
+        for side in 0..2 {
+            let can_castle = if side == 0 {
+                board.castling.can_castle_kingside(us)
+            } else {
+                board.castling.can_castle_queenside(us)
+            };
+            if !can_castle {
+                continue;
+            }
 
-    /// Check if a square is attacked with custom occupancy
-    fn is_square_attacked_with_occ(board: &Board, sq: Square, by_color: Color, occ: Bitboard) -> bool {
-        let attackers = board.color(by_color);
+            let rook_file = board.castling_rook_files[us.index()][side];
+            let rook_from = Square::from_file_rank(rook_file, back_rank);
+            let king_to_file = if side == 0 { 6 } else { 2 };
+            let rook_to_file = if side == 0 { 5 } else { 3 };
+            let king_to = Square::from_file_rank(king_to_file, back_rank);
+            let rook_to = Square::from_file_rank(rook_to_file, back_rank);
 
-        // Pawn attacks
-        let pawn_attacks = Self::pawn_attacks(sq, by_color.opposite());
-        if (pawn_attacks & board.pieces(PieceType::Pawn) & attackers).is_not_empty() {
-            return true;
-        }
+            let king_travel = Self::file_range(back_rank, king_from.file(), king_to_file);
+            let rook_travel = Self::file_range(back_rank, rook_file, rook_to_file);
+            let must_be_clear = (king_travel | rook_travel) & !king_from.bitboard() & !rook_from.bitboard();
 
-        // Knight attacks
-        let knight_attacks = Self::knight_attacks(sq);
-        if (knight_attacks & board.pieces(PieceType::Knight) & attackers).is_not_empty() {
-            return true;
-        }
+            if (occupied & must_be_clear).is_not_empty() {
+                continue;
+            }
+            if filter_unsafe && (king_travel & danger).is_not_empty() {
+                continue;
+            }
 
-        // Bishop/Queen diagonal attacks
-        let bishop_attacks = Self::bishop_attacks(sq, occ);
-        if (bishop_attacks & (board.pieces(PieceType::Bishop) | board.pieces(PieceType::Queen)) & attackers).is_not_empty() {
-            return true;
+            moves.push(Move::new_castling(king_from, king_to));
         }
+    }
 
-        // Rook/Queen orthogonal attacks
-        let rook_attacks = Self::rook_attacks(sq, occ);
-        if (rook_attacks & (board.pieces(PieceType::Rook) | board.pieces(PieceType::Queen)) & attackers).is_not_empty() {
-            return true;
+    /// All squares on `rank` between files `a` and `b`, inclusive of both ends.
+    fn file_range(rank: u8, a: u8, b: u8) -> Bitboard {
+        let (lo, hi) = (a.min(b), a.max(b));
+        let mut bb = Bitboard::EMPTY;
+        for f in lo..=hi {
+            bb.set(Square::from_file_rank(f, rank).0);
         }
+        bb
+    }
 
-        // King attacks
-        let king_attacks = Self::king_attacks(sq);
-        if (king_attacks & board.pieces(PieceType::King) & attackers).is_not_empty() {
-            return true;
+    /// Every square attacked by `by_color`'s pieces given `occ` as the
+    /// board occupancy, computed in one pass instead of a fresh
+    /// pawn/knight/slider/king attack query per candidate destination.
+    /// Callers checking "is my king safe here" should pass an `occ` with
+    /// the king already removed, so that squares behind the king along a
+    /// slider's ray are still marked as danger — otherwise the king could
+    /// illegally "hide" behind itself from a checking rook or bishop.
+    pub fn attacked_squares(board: &Board, by_color: Color, occ: Bitboard) -> Bitboard {
+        let mut danger = Bitboard::EMPTY;
+
+        for sq in board.pieces_of(PieceType::Pawn, by_color).iter() {
+            danger |= Self::pawn_attacks(Square(sq), by_color);
         }
+        for sq in board.pieces_of(PieceType::Knight, by_color).iter() {
+            danger |= Self::knight_attacks(Square(sq));
+        }
+        let diagonal_sliders = board.pieces_of(PieceType::Bishop, by_color) | board.pieces_of(PieceType::Queen, by_color);
+        for sq in diagonal_sliders.iter() {
+            danger |= Self::bishop_attacks(Square(sq), occ);
+        }
+        let orthogonal_sliders = board.pieces_of(PieceType::Rook, by_color) | board.pieces_of(PieceType::Queen, by_color);
+        for sq in orthogonal_sliders.iter() {
+            danger |= Self::rook_attacks(Square(sq), occ);
+        }
+        danger |= Self::king_attacks(board.king_square(by_color));
 
-        false
+        danger
     }
 }
 
@@ -920,6 +1472,34 @@ mod tests {
         assert_eq!(attacks.count(), 3);
     }
 
+    #[test]
+    fn test_magic_rook_and_bishop_attacks_match_ray_walk() {
+        // Cross-check the magic-bitboard lookup against the ray-walking
+        // reference it was built from, for a handful of occupancies per
+        // square (full exhaustive coverage is `build.rs`'s own job).
+        let mg = MoveGen::instance();
+        let occupancies = [
+            Bitboard::EMPTY,
+            Bitboard(0x0000_0010_0010_0000),
+            Bitboard(0xFFFF_FFFF_FFFF_FFFF),
+            Bitboard(0x0081_0000_2400_8001),
+        ];
+        for sq in 0..64u8 {
+            for &occ in &occupancies {
+                assert_eq!(
+                    MoveGen::rook_attacks(Square(sq), occ),
+                    mg.sliding_attacks(sq, occ.0, true),
+                    "rook attacks mismatch on square {sq} with occupancy {occ:?}"
+                );
+                assert_eq!(
+                    MoveGen::bishop_attacks(Square(sq), occ),
+                    mg.sliding_attacks(sq, occ.0, false),
+                    "bishop attacks mismatch on square {sq} with occupancy {occ:?}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_rook_attacks_empty_board() {
         let attacks = MoveGen::rook_attacks(Square::E4, Bitboard::EMPTY);
@@ -942,17 +1522,29 @@ mod tests {
     #[test]
     fn test_perft_initial() {
         let board = Board::startpos();
-        assert_eq!(perft(&board, 1), 20);
-        assert_eq!(perft(&board, 2), 400);
-        assert_eq!(perft(&board, 3), 8902);
+        assert_eq!(MoveGen::perft(&board, 1), 20);
+        assert_eq!(MoveGen::perft(&board, 2), 400);
+        assert_eq!(MoveGen::perft(&board, 3), 8902);
     }
 
     #[test]
     fn test_perft_kiwipete() {
         // Famous test position
         let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
-        assert_eq!(perft(&board, 1), 48);
-        assert_eq!(perft(&board, 2), 2039);
+        assert_eq!(MoveGen::perft(&board, 1), 48);
+        assert_eq!(MoveGen::perft(&board, 2), 2039);
+    }
+
+    #[test]
+    fn test_perft_divide_initial_depth_2() {
+        // Each of the 20 root moves from the startpos should divide into
+        // exactly 20 replies, since every first move leaves 20 legal moves
+        // for the opponent at depth 1.
+        let board = Board::startpos();
+        let divided = MoveGen::perft_divide(&board, 2);
+        assert_eq!(divided.len(), 20);
+        assert_eq!(divided.iter().map(|&(_, nodes)| nodes).sum::<u64>(), 400);
+        assert!(divided.iter().all(|&(_, nodes)| nodes == 20));
     }
 
     #[test]
@@ -971,6 +1563,55 @@ mod tests {
         assert_eq!(castle_moves.len(), 2); // Both kingside and queenside
     }
 
+    /// Build a Chess960 board with the king on b1/b8 and rooks on a1/h1
+    /// (and the mirrors) from a plain `KQkq` FEN, since `Board::from_fen`
+    /// only switches to Chess960 mode for Shredder-FEN castling letters.
+    fn chess960_board(fen: &str) -> Board {
+        use crate::core::board::CastlingMode;
+        let mut board = Board::from_fen(fen).unwrap();
+        board.castling_mode = CastlingMode::Chess960;
+        board.castling_king_files = [1, 1];
+        board.castling_rook_files = [[7, 0], [7, 0]];
+        board
+    }
+
+    #[test]
+    fn test_chess960_castling_generates_both_sides() {
+        let board = chess960_board("rk5r/pppppppp/8/8/8/8/PPPPPPPP/RK5R w KQkq - 0 1");
+        let moves = MoveGen::generate_legal_moves(&board);
+        let castle_moves: Vec<_> = moves.iter().filter(|m| m.is_castling()).collect();
+        assert_eq!(castle_moves.len(), 2);
+        assert!(castle_moves.iter().any(|m| m.from() == Square::B1 && m.to() == Square::G1));
+        assert!(castle_moves.iter().any(|m| m.from() == Square::B1 && m.to() == Square::C1));
+    }
+
+    #[test]
+    fn test_chess960_castling_blocked_by_intervening_piece() {
+        // A knight on e1 sits between the king and the kingside rook;
+        // only queenside castling should remain legal.
+        let board = chess960_board("rk5r/pppppppp/8/8/8/8/PPPP1PPP/RK2N2R w KQkq - 0 1");
+        let moves = MoveGen::generate_legal_moves(&board);
+        let castle_moves: Vec<_> = moves.iter().filter(|m| m.is_castling()).collect();
+        assert_eq!(castle_moves.len(), 1);
+        assert_eq!(castle_moves[0].to(), Square::C1);
+    }
+
+    #[test]
+    fn test_chess960_castling_make_unmake_round_trip() {
+        let mut board = chess960_board("rk5r/pppppppp/8/8/8/8/PPPPPPPP/RK5R w KQkq - 0 1");
+        let before = board.to_fen();
+        let kingside = Move::new_castling(Square::B1, Square::G1);
+
+        let undo = board.make_move(kingside).expect("kingside castle should be legal");
+        assert_eq!(board.piece_at[Square::G1.index()].unwrap().piece_type, PieceType::King);
+        assert_eq!(board.piece_at[Square::F1.index()].unwrap().piece_type, PieceType::Rook);
+        assert!(board.piece_at[Square::B1.index()].is_none());
+        assert!(board.piece_at[Square::H1.index()].is_none());
+
+        board.unmake_move(kingside, undo);
+        assert_eq!(board.to_fen(), before);
+    }
+
     #[test]
     fn test_promotion() {
         let board = Board::from_fen("8/P7/8/8/8/8/8/4K2k w - - 0 1").unwrap();
@@ -979,23 +1620,144 @@ mod tests {
         assert_eq!(promo_moves.len(), 4); // Q, R, B, N
     }
 
-    /// Perft function for testing move generation correctness
-    fn perft(board: &Board, depth: u32) -> u64 {
-        if depth == 0 {
-            return 1;
+    #[test]
+    fn test_duck_blocks_sliding_attacks() {
+        // Rook on a1, duck on a4: the rook can't see past the duck.
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        board.duck = Some(Square::A4);
+        let moves = MoveGen::generate_legal_moves(&board);
+        let rook_moves: Vec<_> = moves.iter().filter(|m| m.from() == Square::A1).collect();
+        // a2, a3 (blocked at a4), b1, c1, d1 (blocked by own king at e1)
+        assert_eq!(rook_moves.len(), 5);
+        assert!(!rook_moves.iter().any(|m| m.to() == Square::A4));
+    }
+
+    #[test]
+    fn test_king_cannot_flee_along_checking_rook_ray() {
+        // Black rook on e8 checks the white king on e1 down the e-file;
+        // e2 is behind the king on that same ray and must stay unsafe.
+        let board = Board::from_fen("k3r3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let moves = MoveGen::generate_legal_moves(&board);
+        assert!(!moves.iter().any(|m| m.from() == Square::E1 && m.to() == Square::E2));
+        // Stepping off the e-file is fine.
+        assert!(moves.iter().any(|m| m.from() == Square::E1 && m.to() == Square::D1));
+    }
+
+    #[test]
+    fn test_attacked_squares_matches_per_square_is_attacked() {
+        let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let occ = board.occupied();
+        let danger = MoveGen::attacked_squares(&board, Color::Black, occ);
+        for sq in 0..64u8 {
+            assert_eq!(
+                danger.contains(sq),
+                board.is_attacked(Square(sq), Color::Black),
+                "mismatch on square {sq}"
+            );
         }
+    }
 
-        let moves = MoveGen::generate_legal_moves(board);
-        let mut nodes = 0;
+    #[test]
+    fn test_duck_not_a_legal_target() {
+        // Knight on b1 could normally reach a3/c3/d2; putting the duck on
+        // one of those squares must remove it from the destination set.
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1").unwrap();
+        board.duck = Some(Square::A3);
+        let moves = MoveGen::generate_legal_moves(&board);
+        let knight_moves: Vec<_> = moves.iter().filter(|m| m.from() == Square::B1).collect();
+        assert!(!knight_moves.iter().any(|m| m.to() == Square::A3));
+    }
 
-        for mv in moves.iter() {
-            let mut new_board = board.clone();
-            if new_board.make_move(*mv) {
-                nodes += perft(&new_board, depth - 1);
-            }
+    #[test]
+    fn test_generate_duck_moves_excludes_occupied_and_duck_square() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        board.duck = Some(Square::D4);
+        let duck_moves = MoveGen::generate_duck_moves(&board);
+
+        // 64 squares minus the two kings minus the duck's own square.
+        assert_eq!(duck_moves.len(), 61);
+        assert!(!duck_moves.iter().any(|m| m.to() == Square::D4));
+        assert!(!duck_moves.iter().any(|m| m.to() == Square::E1));
+        assert!(!duck_moves.iter().any(|m| m.to() == Square::E8));
+        assert!(duck_moves.iter().all(|m| m.from() == Square::D4));
+    }
+
+    #[test]
+    fn test_duck_chess_ignores_pins() {
+        // Bishop on d2 is pinned to the king on e1 by the black bishop on
+        // b4 under orthodox rules; DuckChess has no check, so the pin
+        // doesn't restrict it.
+        let board = Board::from_fen("7k/8/8/8/1b6/8/3B4/4K2r w - - 0 1").unwrap();
+        let moves = MoveGen::generate_duck_chess_moves(&board);
+        assert!(moves.iter().any(|m| m.from() == Square::D2 && m.to() == Square::C1));
+    }
+
+    #[test]
+    fn test_duck_chess_king_can_step_into_check_and_capture() {
+        // Rook on e2 would ordinarily put the king in check and forbid
+        // most king moves off the back rank; DuckChess has no check, so
+        // the king freely captures it or steps anywhere else instead.
+        let board = Board::from_fen("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1").unwrap();
+        let moves = MoveGen::generate_duck_chess_moves(&board);
+        assert!(moves
+            .iter()
+            .any(|m| m.from() == Square::E1 && m.to() == Square::E2 && m.is_capture()));
+        assert!(moves.iter().any(|m| m.from() == Square::E1 && m.to() == Square::D1));
+    }
+
+    #[test]
+    fn test_captures_and_quiets_partition_legal_moves() {
+        // Captures and Quiets should be disjoint and together cover every
+        // legal move, for both a quiet-ish position and a tactical one.
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let legal = MoveGen::generate(&board, GenType::Legal);
+            let captures = MoveGen::generate(&board, GenType::Captures);
+            let quiets = MoveGen::generate(&board, GenType::Quiets);
+
+            assert!(captures.iter().all(|m| m.is_capture()));
+            assert!(quiets.iter().all(|m| !m.is_capture()));
+            assert_eq!(captures.len() + quiets.len(), legal.len());
         }
+    }
 
-        nodes
+    #[test]
+    fn test_non_evasions_matches_legal_when_not_in_check() {
+        let board = Board::startpos();
+        assert!(board.checkers.is_empty());
+        let legal = MoveGen::generate(&board, GenType::Legal);
+        let non_evasions = MoveGen::generate(&board, GenType::NonEvasions);
+        assert_eq!(legal.len(), non_evasions.len());
+    }
+
+    #[test]
+    fn test_evasions_matches_legal_when_in_check() {
+        // Black rook on e2 checks the white king on e1; every legal reply
+        // must come from the Evasions target mask.
+        let board = Board::from_fen("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1").unwrap();
+        assert!(!board.checkers.is_empty());
+        let legal = MoveGen::generate(&board, GenType::Legal);
+        let evasions = MoveGen::generate(&board, GenType::Evasions);
+        assert_eq!(legal.len(), evasions.len());
+    }
+
+    #[test]
+    fn test_duck_chess_pairs_every_move_with_a_duck_relocation() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let piece_moves = MoveGen::generate_pseudo_legal_no_check(&board);
+        let duck_moves = MoveGen::generate_duck_chess_moves(&board);
+
+        // Every piece move lands on a board with exactly 62 empty squares
+        // left (64 minus the two kings, one of which just relocated).
+        assert_eq!(duck_moves.len(), piece_moves.len() * 62);
+        for mv in piece_moves.iter() {
+            assert!(duck_moves
+                .iter()
+                .all(|paired| paired.from() != mv.from() || paired.to() != mv.to() || paired.duck_to().is_some()));
+        }
     }
 }
 
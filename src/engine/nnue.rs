@@ -1,21 +1,49 @@
 //! NNUE (Efficiently Updatable Neural Network) Evaluation
 //!
-//! This implements a simple NNUE architecture for chess evaluation.
-//! The network uses a HalfKP feature set where features are indexed by:
-//! (king_square, piece_square, piece_type, piece_color)
+//! This implements a HalfKA_v2_hm-style NNUE architecture for chess
+//! evaluation. Features are indexed by:
+//! (king_bucket, piece_square, piece_type_including_color)
+//! where `king_bucket` groups the perspective king's square after
+//! exploiting left/right board symmetry: a king on the e-h files is
+//! mirrored onto a-d before indexing, so every feature set only ever
+//! needs to represent a king on half the board. Unlike HalfKP, the king's
+//! own square is itself one of the 12 piece planes ("KA" = king + all
+//! pieces, not just pawns-and-up).
 //!
 //! Architecture:
-//! - Input: 768 features per perspective (64 king squares * 12 piece types)
+//! - Input: KING_BUCKETS * 64 squares * 12 piece planes per perspective
 //! - Hidden Layer 1: 256 neurons (ClippedReLU activation)
 //! - Hidden Layer 2: 32 neurons (ClippedReLU activation)
 //! - Output: 1 neuron (evaluation score)
 
+use super::movegen::MoveGen;
+use super::simd;
+use crate::core::bitboard::Bitboard;
 use crate::core::board::{Board, Color, Piece, PieceType, Square};
 
-/// Number of input features per side (simplified HalfKP)
-/// 64 squares * 10 piece types (excluding kings) = 640 per perspective
-/// We use a simpler 768-feature set: 64 squares * 12 pieces
-pub const INPUT_SIZE: usize = 768;
+/// Number of buckets the perspective king's square is grouped into.
+/// Horizontal mirroring (see [`NNUENetwork::feature_index`]) always puts
+/// the king on files a-d, leaving 4 files * 8 ranks = 32 distinct squares.
+pub const KING_BUCKETS: usize = 32;
+
+/// Lookup from a (mirrored) king square to its bucket index. Computed at
+/// compile time rather than hand-enumerated; only the a-d half is ever
+/// looked up, but the table covers all 64 squares for simplicity.
+const KING_BUCKET_TABLE: [usize; 64] = {
+    let mut table = [0usize; 64];
+    let mut sq = 0usize;
+    while sq < 64 {
+        let file = sq % 8;
+        let rank = sq / 8;
+        table[sq] = rank * 4 + (file % 4);
+        sq += 1;
+    }
+    table
+};
+
+/// Number of input features per perspective: one plane of 64 squares * 12
+/// pieces per king bucket.
+pub const INPUT_SIZE: usize = KING_BUCKETS * 64 * 12;
 
 /// Hidden layer 1 size
 pub const HIDDEN1_SIZE: usize = 256;
@@ -30,6 +58,192 @@ pub const OUTPUT_SIZE: usize = 1;
 pub const WEIGHT_SCALE: i32 = 64;
 pub const ACTIVATION_SCALE: i32 = 127;
 
+/// Denominator for [`endgame_scale_factor`]'s out-of-64 draw scaling.
+const FULL_SCALE: i32 = 64;
+
+/// Base scale (out of [`FULL_SCALE`]) for a pure opposite-colored-bishop
+/// ending with no pawns left to create a passed-pawn race.
+const OCB_BASE_SCALE: i32 = 36;
+
+/// Scale factor, out of [`FULL_SCALE`], that `evaluate_simple` multiplies
+/// its raw centipawn score by. Below `FULL_SCALE` for material
+/// configurations that are drawish independent of the eval's material
+/// count: opposite-colored-bishop endings, and a stronger side left with
+/// theoretically insufficient mating material.
+fn endgame_scale_factor(board: &Board) -> i32 {
+    let total_pawns = board.pieces(PieceType::Pawn).count() as i32;
+
+    // Opposite-colored bishops: each side has exactly one bishop and
+    // they're on opposite square colors. The fewer pawns left to run a
+    // passed-pawn race with, the harder the ending is to convert.
+    if let (Some(white_light), Some(black_light)) = (
+        board.bishop_color_parity(Color::White),
+        board.bishop_color_parity(Color::Black),
+    ) {
+        if white_light != black_light {
+            let pawn_bonus = (total_pawns * 4).min(FULL_SCALE - OCB_BASE_SCALE);
+            return OCB_BASE_SCALE + pawn_bonus;
+        }
+    }
+
+    // Generic insufficient material: a side with no pawns and at most one
+    // minor piece (and no major) can't force mate regardless of how the
+    // rest of the eval scores the position.
+    let insufficient = |color: Color| {
+        board.pieces_of(PieceType::Pawn, color).is_empty()
+            && board.pieces_of(PieceType::Queen, color).is_empty()
+            && board.pieces_of(PieceType::Rook, color).is_empty()
+            && (board.pieces_of(PieceType::Knight, color) | board.pieces_of(PieceType::Bishop, color)).count() <= 1
+    };
+    let material = |color: Color| {
+        board.pieces_of(PieceType::Pawn, color).count() as i32 * 100
+            + board.pieces_of(PieceType::Knight, color).count() as i32 * 320
+            + board.pieces_of(PieceType::Bishop, color).count() as i32 * 330
+            + board.pieces_of(PieceType::Rook, color).count() as i32 * 500
+            + board.pieces_of(PieceType::Queen, color).count() as i32 * 900
+    };
+    let stronger = if material(Color::White) >= material(Color::Black) { Color::White } else { Color::Black };
+    if insufficient(stronger) {
+        return 16;
+    }
+
+    FULL_SCALE
+}
+
+/// Per-piece-type weight used by [`king_safety_term`] when tallying
+/// enemy pieces attacking into a king's ring; roughly proportional to
+/// how dangerous each piece type is once it gets close to the king.
+/// Indexed by `PieceType`; pawns and kings aren't counted as ring
+/// attackers here.
+const KING_ATTACK_WEIGHT: [i32; 6] = [0, 2, 2, 3, 5, 0];
+
+/// Added to a king's danger score per ring square the enemy attacks more
+/// than the king's own side defends.
+const WEAK_SQUARE_DANGER: i32 = 3;
+
+/// Added to a king's danger score per open (no pawns at all) or
+/// semi-open (no pawn of the king's own color) file on or adjacent to it.
+const OPEN_FILE_DANGER: i32 = 4;
+const SEMI_OPEN_FILE_DANGER: i32 = 2;
+
+/// Central files the space term weighs, matching the c/d/e/f files a
+/// middlegame plan usually fights over.
+const CENTRAL_FILES: [u8; 4] = [2, 3, 4, 5];
+
+/// Non-pawn material (knights/bishops/rooks, centipawns) below which
+/// [`is_late_endgame`] gates king safety and space off, since basic mates
+/// (KR v K, KBN v K, ...) shouldn't be distorted by either term.
+const LATE_ENDGAME_MATERIAL: i32 = 500 + 320;
+
+/// True once queens are off and there's less than a rook-and-knight's
+/// worth of non-pawn material left on the board.
+fn is_late_endgame(board: &Board) -> bool {
+    if board.pieces(PieceType::Queen).is_not_empty() {
+        return false;
+    }
+    let non_pawn_material = board.pieces(PieceType::Knight).count() as i32 * 320
+        + board.pieces(PieceType::Bishop).count() as i32 * 330
+        + board.pieces(PieceType::Rook).count() as i32 * 500;
+    non_pawn_material < LATE_ENDGAME_MATERIAL
+}
+
+/// Raw (unsigned) danger score for `color`'s king: a weighted count of
+/// enemy pieces attacking into the king ring (king square + its 8
+/// neighbors), ring squares the enemy attacks more than the king's own
+/// side defends, and open/semi-open files on or adjacent to the king.
+/// Split out from [`king_safety_term`] so the danger accumulation itself
+/// can be exercised without also reasoning about the quadratic scaling.
+fn king_danger(board: &Board, color: Color) -> i32 {
+    let enemy = color.opposite();
+    let king_sq = board.king_square(color);
+    let ring = MoveGen::king_attacks(king_sq) | Bitboard::from_square(king_sq.0);
+    let occ = board.occupied();
+
+    let mut danger = 0i32;
+    for &pt in &[PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen] {
+        for sq in board.pieces_of(pt, enemy).iter() {
+            let attacks = match pt {
+                PieceType::Knight => MoveGen::knight_attacks(Square(sq)),
+                PieceType::Bishop => MoveGen::bishop_attacks(Square(sq), occ),
+                PieceType::Rook => MoveGen::rook_attacks(Square(sq), occ),
+                PieceType::Queen => MoveGen::queen_attacks(Square(sq), occ),
+                _ => unreachable!(),
+            };
+            if (attacks & ring).is_not_empty() {
+                danger += KING_ATTACK_WEIGHT[pt as usize];
+            }
+        }
+    }
+
+    // Ring squares the enemy attacks but the king's own side doesn't:
+    // the classic "weak square next to the king" pattern.
+    let enemy_attacks = MoveGen::attacked_squares(board, enemy, occ);
+    let own_attacks = MoveGen::attacked_squares(board, color, occ);
+    danger += (ring & enemy_attacks & !own_attacks).count() as i32 * WEAK_SQUARE_DANGER;
+
+    // Open/semi-open files on or adjacent to the king.
+    let king_file = king_sq.file() as i32;
+    for file in (king_file - 1)..=(king_file + 1) {
+        if !(0..8).contains(&file) {
+            continue;
+        }
+        let file_mask = Bitboard::file_mask(file as u8);
+        let own_pawns = (board.pieces_of(PieceType::Pawn, color) & file_mask).is_not_empty();
+        let enemy_pawns = (board.pieces_of(PieceType::Pawn, enemy) & file_mask).is_not_empty();
+        if !own_pawns && !enemy_pawns {
+            danger += OPEN_FILE_DANGER;
+        } else if !own_pawns {
+            danger += SEMI_OPEN_FILE_DANGER;
+        }
+    }
+
+    danger
+}
+
+/// King-safety term for `color`'s king, already signed from White's
+/// perspective: [`king_danger`] converted into a quadratically growing
+/// penalty (`danger * danger / 512`) subtracted from the defending side.
+fn king_safety_term(board: &Board, color: Color) -> i32 {
+    let penalty = king_danger(board, color).pow(2) / 512;
+    if color == Color::White { -penalty } else { penalty }
+}
+
+/// Space term for `color`: counts safe squares (not attacked by an enemy
+/// pawn, not occupied by one of `color`'s own pieces) in the four
+/// central files on `color`'s own half of the board, weighted up by how
+/// many pieces `color` has in play and how many of its pawns are
+/// blocked, since a cramped, blocked position is exactly when having
+/// more room to maneuver into matters most. Unsigned; callers combine
+/// both sides' space into a single White-perspective term themselves.
+fn space_term(board: &Board, color: Color) -> i32 {
+    let enemy = color.opposite();
+    let own_half = match color {
+        Color::White => Bitboard::RANK_1 | Bitboard::RANK_2 | Bitboard::RANK_3 | Bitboard::RANK_4,
+        Color::Black => Bitboard::RANK_5 | Bitboard::RANK_6 | Bitboard::RANK_7 | Bitboard::RANK_8,
+    };
+    let mut central_files = Bitboard::EMPTY;
+    for &file in &CENTRAL_FILES {
+        central_files |= Bitboard::file_mask(file);
+    }
+
+    let mut enemy_pawn_attacks = Bitboard::EMPTY;
+    for sq in board.pieces_of(PieceType::Pawn, enemy).iter() {
+        enemy_pawn_attacks |= MoveGen::pawn_attacks(Square(sq), enemy);
+    }
+
+    let safe_squares = central_files & own_half & !enemy_pawn_attacks & !board.color(color);
+
+    let own_pawns = board.pieces_of(PieceType::Pawn, color);
+    let occ = board.occupied();
+    let blocked_pawns = match color {
+        Color::White => (own_pawns.north() & occ).count() as i32,
+        Color::Black => (own_pawns.south() & occ).count() as i32,
+    };
+    let weight = 1 + board.color(color).count() as i32 / 4 + blocked_pawns / 2;
+
+    safe_squares.count() as i32 * weight
+}
+
 /// NNUE network weights and biases
 pub struct NNUENetwork {
     /// Input -> Hidden1 weights [INPUT_SIZE][HIDDEN1_SIZE]
@@ -62,23 +276,25 @@ impl NNUENetwork {
         // This gives the engine basic understanding without training
         let piece_values = [100, 320, 330, 500, 900, 0]; // P, N, B, R, Q, K
         
-        for sq in 0..64 {
-            let file = sq % 8;
-            let rank = sq / 8;
-            let center_dist = ((3.5 - file as f32).abs() + (3.5 - rank as f32).abs()) as i16;
-            
-            for piece in 0..12 {
-                let piece_type = piece % 6;
-                let color = piece / 6;
-                let feature_idx = sq * 12 + piece;
-                
-                let base_value = piece_values[piece_type] as i16;
-                let sign = if color == 0 { 1 } else { -1 };
-                
-                // Distribute piece value across hidden neurons with some variation
-                for h in 0..HIDDEN1_SIZE {
-                    let variation = ((h as i32 * 17 + feature_idx as i32 * 31) % 21) as i16 - 10;
-                    input_weights[feature_idx][h] = (sign * (base_value / 4 + variation - center_dist)) as i16;
+        for bucket in 0..KING_BUCKETS {
+            for sq in 0..64 {
+                let file = sq % 8;
+                let rank = sq / 8;
+                let center_dist = ((3.5 - file as f32).abs() + (3.5 - rank as f32).abs()) as i16;
+
+                for piece in 0..12 {
+                    let piece_type = piece % 6;
+                    let color = piece / 6;
+                    let feature_idx = (bucket * 64 + sq) * 12 + piece;
+
+                    let base_value = piece_values[piece_type] as i16;
+                    let sign = if color == 0 { 1 } else { -1 };
+
+                    // Distribute piece value across hidden neurons with some variation
+                    for h in 0..HIDDEN1_SIZE {
+                        let variation = ((h as i32 * 17 + feature_idx as i32 * 31) % 21) as i16 - 10;
+                        input_weights[feature_idx][h] = (sign * (base_value / 4 + variation - center_dist)) as i16;
+                    }
                 }
             }
         }
@@ -105,16 +321,212 @@ impl NNUENetwork {
         }
     }
 
-    /// Get feature index for a piece on a square from a perspective
+    /// Get the HalfKA_v2_hm feature index for a piece on a square, relative
+    /// to `perspective` and that perspective's own king square.
+    ///
+    /// The board is first flipped vertically for Black's perspective (as
+    /// with the old HalfKP set), then mirrored horizontally whenever the
+    /// perspective king sits on the e-h files, so the king bucket table
+    /// only ever needs to cover the a-d half of the board.
     #[inline]
-    pub fn feature_index(piece: Piece, sq: Square, perspective: Color) -> usize {
+    pub fn feature_index(piece: Piece, sq: Square, king_sq: Square, perspective: Color) -> usize {
         let piece_idx = piece.color as usize * 6 + piece.piece_type as usize;
-        let sq_idx = if perspective == Color::White {
-            sq.index()
+
+        let (rel_sq, rel_king) = if perspective == Color::White {
+            (sq, king_sq)
         } else {
-            sq.flip_vertical().index()
+            (sq.flip_vertical(), king_sq.flip_vertical())
         };
-        sq_idx * 12 + piece_idx
+        let (rel_sq, rel_king) = if rel_king.file() >= 4 {
+            (rel_sq.flip_horizontal(), rel_king.flip_horizontal())
+        } else {
+            (rel_sq, rel_king)
+        };
+
+        let bucket = KING_BUCKET_TABLE[rel_king.index()];
+        (bucket * 64 + rel_sq.index()) * 12 + piece_idx
+    }
+
+    /// Load a trained network from a Stockfish-style serialized `.nnue`
+    /// file on disk. See [`NNUENetwork::from_bytes`] for the format.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, NNUELoadError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse a Stockfish-style serialized network: a header (`version` as
+    /// u32, `hash` as u32, then a length-prefixed architecture string like
+    /// `"24576x256x32x1"`), followed by the feature transformer (HIDDEN1_SIZE
+    /// biases as i16, then INPUT_SIZE x HIDDEN1_SIZE weights as i16, all
+    /// little-endian), followed by each affine layer as a bias row of i32
+    /// and a weight matrix of i8 in row-major output x input order (first
+    /// hidden1*2 -> hidden2, then hidden2 -> output).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, NNUELoadError> {
+        let mut r = ByteReader::new(bytes);
+
+        let _version = r.read_u32()?;
+        let _hash = r.read_u32()?;
+        let arch_len = r.read_u32()? as usize;
+        let arch = std::str::from_utf8(r.read_bytes(arch_len)?)
+            .map_err(|_| NNUELoadError::InvalidArchString)?;
+        Self::validate_arch(arch)?;
+
+        let hidden1_biases = r.read_i16_vec(HIDDEN1_SIZE)?;
+        let mut input_weights = vec![vec![0i16; HIDDEN1_SIZE]; INPUT_SIZE];
+        for feature in input_weights.iter_mut() {
+            for w in feature.iter_mut() {
+                *w = r.read_i16()?;
+            }
+        }
+
+        // hidden1*2 -> hidden2
+        let (hidden2_biases_i32, hidden2_rows) = r.read_affine_layer(HIDDEN2_SIZE, HIDDEN1_SIZE * 2)?;
+        let hidden2_biases: Vec<i16> = hidden2_biases_i32.iter().map(|&b| b as i16).collect();
+        let mut hidden2_weights = vec![vec![0i16; HIDDEN2_SIZE]; HIDDEN1_SIZE * 2];
+        for (out, row) in hidden2_rows.iter().enumerate() {
+            for (inp, &w) in row.iter().enumerate() {
+                hidden2_weights[inp][out] = w as i16;
+            }
+        }
+
+        // hidden2 -> output
+        let (output_biases_i32, output_rows) = r.read_affine_layer(OUTPUT_SIZE, HIDDEN2_SIZE)?;
+        let output_bias = output_biases_i32[0] as i16;
+        let mut output_weights = vec![0i16; HIDDEN2_SIZE];
+        for (inp, &w) in output_rows[0].iter().enumerate() {
+            output_weights[inp] = w as i16;
+        }
+
+        Ok(NNUENetwork {
+            input_weights,
+            hidden1_biases,
+            hidden2_weights,
+            hidden2_biases,
+            output_weights,
+            output_bias,
+        })
+    }
+
+    /// Check the architecture string (`"<input>x<hidden1>x<hidden2>x<output>"`)
+    /// against this build's compiled-in layer sizes.
+    fn validate_arch(arch: &str) -> Result<(), NNUELoadError> {
+        let dims: Vec<&str> = arch.split('x').collect();
+        if dims.len() != 4 {
+            return Err(NNUELoadError::DimensionMismatch {
+                what: "architecture string",
+                expected: 4,
+                found: dims.len(),
+            });
+        }
+
+        let expected = [INPUT_SIZE, HIDDEN1_SIZE, HIDDEN2_SIZE, OUTPUT_SIZE];
+        let names = ["input", "hidden1", "hidden2", "output"];
+        for ((dim, &exp), &what) in dims.iter().zip(expected.iter()).zip(names.iter()) {
+            let found: usize = dim.parse().map_err(|_| NNUELoadError::InvalidArchString)?;
+            if found != exp {
+                return Err(NNUELoadError::DimensionMismatch { what, expected: exp, found });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A byte cursor over a serialized `.nnue` file, reading little-endian
+/// integers and bounds-checking every read against [`NNUELoadError::UnexpectedEof`].
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        ByteReader { data, pos: 0 }
+    }
+
+    fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], NNUELoadError> {
+        let end = self.pos.checked_add(n).ok_or(NNUELoadError::UnexpectedEof)?;
+        let slice = self.data.get(self.pos..end).ok_or(NNUELoadError::UnexpectedEof)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, NNUELoadError> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, NNUELoadError> {
+        Ok(i32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_i16(&mut self) -> Result<i16, NNUELoadError> {
+        Ok(i16::from_le_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    fn read_i8(&mut self) -> Result<i8, NNUELoadError> {
+        Ok(self.read_bytes(1)?[0] as i8)
+    }
+
+    fn read_i16_vec(&mut self, n: usize) -> Result<Vec<i16>, NNUELoadError> {
+        (0..n).map(|_| self.read_i16()).collect()
+    }
+
+    /// One affine layer: a bias row of `out_size` i32s, then an
+    /// `out_size x in_size` weight matrix of i8 in row-major order.
+    fn read_affine_layer(
+        &mut self,
+        out_size: usize,
+        in_size: usize,
+    ) -> Result<(Vec<i32>, Vec<Vec<i8>>), NNUELoadError> {
+        let biases = (0..out_size).map(|_| self.read_i32()).collect::<Result<Vec<_>, _>>()?;
+        let rows = (0..out_size)
+            .map(|_| (0..in_size).map(|_| self.read_i8()).collect::<Result<Vec<_>, _>>())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((biases, rows))
+    }
+}
+
+/// Why [`NNUENetwork::from_file`]/[`NNUENetwork::from_bytes`] failed to
+/// load a serialized network.
+#[derive(Debug)]
+pub enum NNUELoadError {
+    /// Couldn't read the file from disk.
+    Io(std::io::Error),
+    /// The file ended before all declared sections were read.
+    UnexpectedEof,
+    /// The architecture string wasn't valid UTF-8 or wasn't `a x b x c x d`.
+    InvalidArchString,
+    /// A declared layer dimension didn't match this build's compiled-in
+    /// `INPUT_SIZE`/`HIDDEN1_SIZE`/`HIDDEN2_SIZE`/`OUTPUT_SIZE`.
+    DimensionMismatch {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl std::fmt::Display for NNUELoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NNUELoadError::Io(e) => write!(f, "failed to read network file: {}", e),
+            NNUELoadError::UnexpectedEof => {
+                write!(f, "network file ended before all declared sections were read")
+            }
+            NNUELoadError::InvalidArchString => {
+                write!(f, "architecture string is not valid UTF-8 `input x hidden1 x hidden2 x output`")
+            }
+            NNUELoadError::DimensionMismatch { what, expected, found } => {
+                write!(f, "{} has dimension {}, expected {}", what, found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NNUELoadError {}
+
+impl From<std::io::Error> for NNUELoadError {
+    fn from(e: std::io::Error) -> Self {
+        NNUELoadError::Io(e)
     }
 }
 
@@ -134,6 +546,13 @@ pub struct NNUEAccumulator {
     pub black: Vec<i16>,
     /// Whether the accumulator is valid
     pub valid: bool,
+    /// The king squares the accumulator's features were computed against.
+    /// Every feature index depends on its perspective's own king bucket
+    /// (see [`NNUENetwork::feature_index`]), so once either king moves
+    /// every feature in that perspective is stale and must be rebuilt via
+    /// [`Self::refresh`] rather than patched incrementally.
+    white_king: Square,
+    black_king: Square,
 }
 
 impl NNUEAccumulator {
@@ -142,11 +561,16 @@ impl NNUEAccumulator {
             white: vec![0; HIDDEN1_SIZE],
             black: vec![0; HIDDEN1_SIZE],
             valid: false,
+            white_king: Square::E1,
+            black_king: Square::E8,
         }
     }
 
     /// Refresh the accumulator from scratch for a position
     pub fn refresh(&mut self, board: &Board, network: &NNUENetwork) {
+        self.white_king = board.king_square(Color::White);
+        self.black_king = board.king_square(Color::Black);
+
         // Reset to biases
         self.white.copy_from_slice(&network.hidden1_biases);
         self.black.copy_from_slice(&network.hidden1_biases);
@@ -163,24 +587,20 @@ impl NNUEAccumulator {
 
     /// Add a piece to the accumulator
     pub fn add_piece(&mut self, piece: Piece, sq: Square, network: &NNUENetwork) {
-        let white_idx = NNUENetwork::feature_index(piece, sq, Color::White);
-        let black_idx = NNUENetwork::feature_index(piece, sq, Color::Black);
+        let white_idx = NNUENetwork::feature_index(piece, sq, self.white_king, Color::White);
+        let black_idx = NNUENetwork::feature_index(piece, sq, self.black_king, Color::Black);
 
-        for h in 0..HIDDEN1_SIZE {
-            self.white[h] += network.input_weights[white_idx][h];
-            self.black[h] += network.input_weights[black_idx][h];
-        }
+        simd::add_assign(&mut self.white, &network.input_weights[white_idx]);
+        simd::add_assign(&mut self.black, &network.input_weights[black_idx]);
     }
 
     /// Remove a piece from the accumulator
     pub fn remove_piece(&mut self, piece: Piece, sq: Square, network: &NNUENetwork) {
-        let white_idx = NNUENetwork::feature_index(piece, sq, Color::White);
-        let black_idx = NNUENetwork::feature_index(piece, sq, Color::Black);
+        let white_idx = NNUENetwork::feature_index(piece, sq, self.white_king, Color::White);
+        let black_idx = NNUENetwork::feature_index(piece, sq, self.black_king, Color::Black);
 
-        for h in 0..HIDDEN1_SIZE {
-            self.white[h] -= network.input_weights[white_idx][h];
-            self.black[h] -= network.input_weights[black_idx][h];
-        }
+        simd::sub_assign(&mut self.white, &network.input_weights[white_idx]);
+        simd::sub_assign(&mut self.black, &network.input_weights[black_idx]);
     }
 
     /// Move a piece (remove from old square, add to new square)
@@ -188,6 +608,49 @@ impl NNUEAccumulator {
         self.remove_piece(piece, from, network);
         self.add_piece(piece, to, network);
     }
+
+    /// Incrementally update the accumulator for the transition from
+    /// `before` to `after` instead of paying for a full [`Self::refresh`].
+    /// Diffs the two positions square by square and removes/adds only the
+    /// handful of features a single move actually touches (the mover, any
+    /// capture, a rook on castling, a promoted piece, ...), so this works
+    /// for any move without the caller having to classify it first.
+    ///
+    /// Every feature is keyed off its perspective's own king bucket, so a
+    /// king move invalidates every feature for that perspective at once;
+    /// such moves fall back to a full [`Self::refresh`] instead of being
+    /// diffed square by square.
+    ///
+    /// Calling this again with `before` and `after` swapped undoes it
+    /// bit-for-bit, which is what backing out of a search branch needs.
+    pub fn apply_move(&mut self, before: &Board, after: &Board, network: &NNUENetwork) {
+        if is_king_move(before, after) {
+            self.refresh(after, network);
+            return;
+        }
+
+        for sq in 0..64 {
+            let old = before.piece_at[sq];
+            let new = after.piece_at[sq];
+            if old == new {
+                continue;
+            }
+            if let Some(piece) = old {
+                self.remove_piece(piece, Square(sq as u8), network);
+            }
+            if let Some(piece) = new {
+                self.add_piece(piece, Square(sq as u8), network);
+            }
+        }
+    }
+}
+
+/// Whether either side's king square differs between `before` and `after`,
+/// meaning every feature for that side's perspective is stale (see
+/// [`NNUEAccumulator::apply_move`]).
+fn is_king_move(before: &Board, after: &Board) -> bool {
+    before.king_square(Color::White) != after.king_square(Color::White)
+        || before.king_square(Color::Black) != after.king_square(Color::Black)
 }
 
 impl Default for NNUEAccumulator {
@@ -209,6 +672,15 @@ impl NNUEEvaluator {
         }
     }
 
+    /// Replace this evaluator's network with one loaded from a trained
+    /// `.nnue` file (see [`NNUENetwork::from_file`]), for the UCI
+    /// `EvalFile` option. The built-in synthetic weights stay in place
+    /// when no file is configured, or if loading fails.
+    pub fn load_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), NNUELoadError> {
+        self.network = NNUENetwork::from_file(path)?;
+        Ok(())
+    }
+
     /// Clipped ReLU activation function
     #[inline]
     fn clipped_relu(x: i16) -> i16 {
@@ -230,19 +702,20 @@ impl NNUEEvaluator {
             hidden1_output[HIDDEN1_SIZE + i] = Self::clipped_relu(them[i]);
         }
 
-        // Hidden layer 2
-        let mut hidden2 = self.network.hidden2_biases.clone();
+        // Hidden layer 2: accumulate in i32 (SIMD-accelerated; see
+        // `engine::simd::affine_row_accumulate`) and only narrow to i16
+        // once, at the ClippedReLU step below.
+        let mut hidden2_acc: Vec<i32> = self.network.hidden2_biases.iter().map(|&b| b as i32).collect();
         for h1 in 0..(HIDDEN1_SIZE * 2) {
             let activation = hidden1_output[h1] as i32;
-            for h2 in 0..HIDDEN2_SIZE {
-                hidden2[h2] = (hidden2[h2] as i32 + activation * self.network.hidden2_weights[h1][h2] as i32 / WEIGHT_SCALE) as i16;
-            }
+            simd::affine_row_accumulate(&mut hidden2_acc, activation, &self.network.hidden2_weights[h1]);
         }
 
         // Apply ClippedReLU to hidden layer 2
-        for h2 in 0..HIDDEN2_SIZE {
-            hidden2[h2] = Self::clipped_relu(hidden2[h2]);
-        }
+        let hidden2: Vec<i16> = hidden2_acc
+            .iter()
+            .map(|&v| v.clamp(0, ACTIVATION_SCALE) as i16)
+            .collect();
 
         // Output layer
         let mut output = self.network.output_bias as i32;
@@ -313,7 +786,7 @@ impl NNUEEvaluator {
 
         // Penalty for doubled pawns
         for file in 0..8 {
-            let file_mask = crate::core::bitboard::Bitboard::file_mask(file);
+            let file_mask = Bitboard::file_mask(file);
             let white_pawns_on_file = (board.pieces_of(PieceType::Pawn, Color::White) & file_mask).count();
             let black_pawns_on_file = (board.pieces_of(PieceType::Pawn, Color::Black) & file_mask).count();
             if white_pawns_on_file > 1 {
@@ -324,6 +797,20 @@ impl NNUEEvaluator {
             }
         }
 
+        // King safety and space: the two biggest positional gaps this
+        // simple eval otherwise misses. Skipped in late endgames, where
+        // basic mates (KR v K, KBN v K, ...) shouldn't be distorted by
+        // either term.
+        if !is_late_endgame(board) {
+            score += king_safety_term(board, Color::White) + king_safety_term(board, Color::Black);
+            score += space_term(board, Color::White) - space_term(board, Color::Black);
+        }
+
+        // Scale dead-drawn-ish material configurations down toward zero
+        // before handing the raw centipawn score back, so the engine
+        // doesn't happily "win" a theoretical draw on eval alone.
+        score = score * endgame_scale_factor(board) / FULL_SCALE;
+
         // Return score from side to move's perspective
         if board.side_to_move == Color::White {
             score
@@ -369,6 +856,7 @@ pub fn evaluate(board: &Board) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::moves::Move;
 
     #[test]
     fn test_nnue_network_creation() {
@@ -378,6 +866,83 @@ mod tests {
         assert_eq!(network.hidden1_biases.len(), HIDDEN1_SIZE);
     }
 
+    /// Serialize a network of known values in the `from_bytes` format, for
+    /// `test_nnue_from_bytes_*` below to parse back out.
+    fn sample_network_bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&42u32.to_le_bytes()); // version
+        bytes.extend_from_slice(&0xdead_beefu32.to_le_bytes()); // hash
+
+        let arch = format!("{}x{}x{}x{}", INPUT_SIZE, HIDDEN1_SIZE, HIDDEN2_SIZE, OUTPUT_SIZE);
+        bytes.extend_from_slice(&(arch.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(arch.as_bytes());
+
+        for h in 0..HIDDEN1_SIZE {
+            bytes.extend_from_slice(&(h as i16).to_le_bytes());
+        }
+        for f in 0..INPUT_SIZE {
+            for h in 0..HIDDEN1_SIZE {
+                let v = ((f + h) % 7) as i16 - 3;
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+
+        for o in 0..HIDDEN2_SIZE {
+            bytes.extend_from_slice(&(o as i32).to_le_bytes());
+        }
+        for o in 0..HIDDEN2_SIZE {
+            for i in 0..(HIDDEN1_SIZE * 2) {
+                bytes.push((((o + i) % 5) as i8 - 2) as u8);
+            }
+        }
+
+        bytes.extend_from_slice(&7i32.to_le_bytes());
+        for i in 0..HIDDEN2_SIZE {
+            bytes.push(((i % 3) as i8 - 1) as u8);
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn test_nnue_from_bytes_roundtrip() {
+        let network = NNUENetwork::from_bytes(&sample_network_bytes()).expect("valid network parses");
+
+        assert_eq!(network.hidden1_biases[5], 5);
+        assert_eq!(network.input_weights[10][3], ((10 + 3) % 7) as i16 - 3);
+        assert_eq!(network.output_bias, 7);
+        assert_eq!(network.output_weights[4], (4 % 3) as i16 - 1);
+        // hidden2_weights is stored [input][output], transposed from the
+        // file's row-major [output][input] layout.
+        assert_eq!(network.hidden2_weights[3][2], (((2 + 3) % 5) as i8 - 2) as i16);
+    }
+
+    #[test]
+    fn test_nnue_from_bytes_rejects_dimension_mismatch() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        let arch = "1x2x3x4";
+        bytes.extend_from_slice(&(arch.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(arch.as_bytes());
+
+        match NNUENetwork::from_bytes(&bytes) {
+            Err(NNUELoadError::DimensionMismatch { .. }) => {}
+            other => panic!("expected DimensionMismatch, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_nnue_from_bytes_rejects_truncated_file() {
+        // A version field (4 bytes) with nothing after it: the hash read
+        // should run off the end.
+        let bytes = 1u32.to_le_bytes().to_vec();
+        match NNUENetwork::from_bytes(&bytes) {
+            Err(NNUELoadError::UnexpectedEof) => {}
+            other => panic!("expected UnexpectedEof, got {:?}", other.map(|_| ())),
+        }
+    }
+
     #[test]
     fn test_accumulator_refresh() {
         let board = Board::startpos();
@@ -392,14 +957,26 @@ mod tests {
     fn test_feature_index() {
         let piece = Piece::new(PieceType::Pawn, Color::White);
         let sq = Square::E4;
-        
-        let white_idx = NNUENetwork::feature_index(piece, sq, Color::White);
-        let black_idx = NNUENetwork::feature_index(piece, sq, Color::Black);
-        
+
+        let white_idx = NNUENetwork::feature_index(piece, sq, Square::E1, Color::White);
+        let black_idx = NNUENetwork::feature_index(piece, sq, Square::E8, Color::Black);
+
         // Different perspectives should give different indices
         assert_ne!(white_idx, black_idx);
     }
 
+    #[test]
+    fn test_feature_index_mirrors_king_on_e_to_h_files() {
+        let piece = Piece::new(PieceType::Pawn, Color::White);
+
+        // A pawn on a4 seen by a king on b1 (a-d files, no mirroring) vs.
+        // the same pawn on h4 seen by a king on g1 (e-h files, mirrored
+        // onto b1) should land on the identical feature.
+        let unmirrored = NNUENetwork::feature_index(piece, Square::A4, Square::B1, Color::White);
+        let mirrored = NNUENetwork::feature_index(piece, Square::H4, Square::G1, Color::White);
+        assert_eq!(unmirrored, mirrored);
+    }
+
     #[test]
     fn test_evaluate_startpos() {
         let board = Board::startpos();
@@ -420,6 +997,79 @@ mod tests {
         assert!(score > 800, "White up a queen should have high eval: {}", score);
     }
 
+    #[test]
+    fn test_opposite_colored_bishops_scale_down_material_edge() {
+        // White up a pawn with opposite-colored bishops (white's bishop
+        // on c1's dark square, black's on c8's light square): the raw
+        // material+positional score should shrink once scaled, not just
+        // report the unscaled pawn-up advantage.
+        let board = Board::from_fen("2b1k3/8/8/8/8/8/4P3/2B1K3 w - - 0 1").unwrap();
+        let evaluator = NNUEEvaluator::new();
+
+        let scaled = evaluator.evaluate_simple(&board);
+        let scale = endgame_scale_factor(&board);
+
+        assert!(scale < FULL_SCALE, "expected OCB scaling, got factor {}", scale);
+        assert!(scaled > 0, "white should still be better, got {}", scaled);
+        assert!(scaled < 100, "OCB should pull the eval close to a draw, got {}", scaled);
+    }
+
+    #[test]
+    fn test_same_colored_bishops_are_not_scaled() {
+        // Both bishops on dark squares (c1 and f8): not an OCB ending, so
+        // the full-material scale factor applies.
+        let board = Board::from_fen("4kb2/8/8/8/8/8/4P3/2B1K3 w - - 0 1").unwrap();
+        assert_eq!(endgame_scale_factor(&board), FULL_SCALE);
+    }
+
+    #[test]
+    fn test_lone_minor_vs_lone_king_is_scaled_as_insufficient_material() {
+        // A lone white bishop can't mate a lone king no matter the raw
+        // material count the rest of eval assigns it.
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap();
+        assert_eq!(endgame_scale_factor(&board), 16);
+    }
+
+    #[test]
+    fn test_king_danger_penalizes_an_exposed_king() {
+        // A fully pawn-sheltered king with no enemy pieces in range is
+        // perfectly safe; the same king with an enemy queen and rook
+        // bearing down on an open board should come back strictly worse.
+        let safe = Board::from_fen("4k3/8/8/8/8/8/PPPPPPPP/4K3 w - - 0 1").unwrap();
+        let exposed = Board::from_fen("4k3/8/8/8/8/8/3q4/4K2r w - - 0 1").unwrap();
+
+        assert_eq!(king_danger(&safe, Color::White), 0);
+        assert!(king_danger(&exposed, Color::White) > 0);
+        assert!(king_safety_term(&exposed, Color::White) <= king_safety_term(&safe, Color::White));
+    }
+
+    #[test]
+    fn test_space_term_rewards_more_developed_pieces() {
+        // Same empty central files on White's own half, but more pieces
+        // in play should weigh those safe squares more heavily.
+        let few_pieces = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let many_pieces = Board::from_fen("4k3/8/8/8/8/8/8/2NQKBN1 w - - 0 1").unwrap();
+
+        let few_space = space_term(&few_pieces, Color::White);
+        let many_space = space_term(&many_pieces, Color::White);
+
+        assert!(
+            many_space > few_space,
+            "expected more developed pieces to score more space: {} vs {}",
+            many_space,
+            few_space
+        );
+    }
+
+    #[test]
+    fn test_is_late_endgame_gates_on_queens_and_material() {
+        let basic_mate = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 0 1").unwrap();
+        assert!(is_late_endgame(&basic_mate));
+
+        let startpos = Board::startpos();
+        assert!(!is_late_endgame(&startpos));
+    }
+
     #[test]
     fn test_nnue_full_evaluation() {
         let board = Board::startpos();
@@ -430,6 +1080,35 @@ mod tests {
         assert!(score.abs() < 100, "Starting position NNUE eval: {}", score);
     }
 
+    #[test]
+    fn test_accumulator_apply_move_matches_refresh_and_undoes_cleanly() {
+        let network = NNUENetwork::new();
+        let before = Board::startpos();
+        let after = {
+            let mut b = before.clone();
+            let _ = b.make_move(Move::new(Square::E2, Square::E4));
+            b
+        };
+
+        let mut acc = NNUEAccumulator::new();
+        acc.refresh(&before, &network);
+        let original = (acc.white.clone(), acc.black.clone());
+
+        // Incrementally walk forward to `after`: it should match a fresh
+        // refresh of that position exactly, not just the free eval.
+        acc.apply_move(&before, &after, &network);
+        let mut expected = NNUEAccumulator::new();
+        expected.refresh(&after, &network);
+        assert_eq!(acc.white, expected.white);
+        assert_eq!(acc.black, expected.black);
+
+        // And walking back (before/after swapped) restores the original
+        // accumulator bit-for-bit.
+        acc.apply_move(&after, &before, &network);
+        assert_eq!(acc.white, original.0);
+        assert_eq!(acc.black, original.1);
+    }
+
     #[test]
     fn test_clipped_relu() {
         assert_eq!(NNUEEvaluator::clipped_relu(-100), 0);
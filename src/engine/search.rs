@@ -8,11 +8,14 @@
 //! - Late move reductions (LMR)
 //! - Quiescence search
 //! - Aspiration windows
+//! - Adaptive time management
 
-use crate::core::board::{Board, Color, PieceType};
+use crate::core::bitboard::Bitboard;
+use crate::core::board::{Board, Color, Piece, PieceType, Square};
 use super::movegen::MoveGen;
 use crate::core::moves::{Move, MoveList};
-use super::nnue::{evaluate, NNUEAccumulator, NNUEEvaluator};
+use super::nnue::{NNUEAccumulator, NNUEEvaluator};
+use super::tablebase::{Tablebase, Wdl};
 use super::tt::{TTFlag, TranspositionTable};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -28,6 +31,48 @@ pub const DRAW_SCORE: i32 = 0;
 /// Maximum search depth
 pub const MAX_DEPTH: i32 = 64;
 
+/// Score drop (in centipawns) versus the previous iteration that counts as
+/// a "fail-low panic" for adaptive time management, not just noise.
+const TIME_PANIC_MARGIN: i32 = 50;
+
+/// Consecutive stable depths (same best move, no panic) before adaptive
+/// time management starts cutting the search short.
+const TIME_STABLE_DEPTHS: u32 = 3;
+
+/// Once the root move has been stable for `TIME_STABLE_DEPTHS` iterations,
+/// cut the time limit to this percentage of the base target.
+const TIME_STABLE_CUT_PERCENT: u64 = 60;
+
+/// When the root move is unstable (changed, or fail-low panic), let the
+/// time limit grow up to this percentage of the base target.
+const TIME_HARD_CAP_PERCENT: u64 = 250;
+
+/// Only begin a new iteration while elapsed time is below this percentage
+/// of the base target, so we don't start a depth we can't finish.
+const TIME_START_NEXT_PERCENT: u64 = 50;
+
+/// Stockfish-style Lazy SMP depth-skip tables. Helper thread `i` skips
+/// depth `d` in its iterative deepening loop whenever
+/// `((d + SKIP_PHASE[i % 20]) / SKIP_SIZE[i % 20]) % 2 != 0`; the main
+/// thread (`i == 0`) always searches every depth. Spreading skipped depths
+/// this way, rather than just offsetting each helper's start depth, keeps
+/// the whole pool diversified across the length of the search instead of
+/// just at the start.
+const SKIP_SIZE: [i32; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+const SKIP_PHASE: [i32; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+/// Build [`Searcher`]'s LMR magnitude table: `reductions[i] = 21.0 * ln(i)`
+/// for `i >= 1`, `reductions[0] = 0`. Computed once per searcher rather
+/// than as a `const` since floating-point `ln` isn't available in const
+/// contexts.
+fn build_reductions() -> [i32; 64] {
+    let mut table = [0i32; 64];
+    for (i, slot) in table.iter_mut().enumerate().skip(1) {
+        *slot = (21.0 * (i as f64).ln()) as i32;
+    }
+    table
+}
+
 /// Search limits
 #[derive(Clone, Debug)]
 pub struct SearchLimits {
@@ -49,6 +94,13 @@ pub struct SearchLimits {
     pub movestogo: Option<u32>,
     /// Infinite search (until stopped)
     pub infinite: bool,
+    /// Number of Lazy SMP threads to search with. `None` or `Some(1)` runs
+    /// single-threaded via [`Searcher::search`]; anything higher is handled
+    /// by [`Searcher::search_parallel`].
+    pub threads: Option<usize>,
+    /// Transposition table size in MB, applied via [`Searcher::set_hash_size`]
+    /// at the start of the search. `None` leaves the table as it already is.
+    pub hash_mb: Option<usize>,
 }
 
 impl Default for SearchLimits {
@@ -63,6 +115,8 @@ impl Default for SearchLimits {
             binc: None,
             movestogo: None,
             infinite: false,
+            threads: None,
+            hash_mb: None,
         }
     }
 }
@@ -74,6 +128,49 @@ pub struct SearchStats {
     pub qnodes: u64,
     pub tt_hits: u64,
     pub tt_cutoffs: u64,
+    /// Deepest iteration fully completed before the search stopped
+    pub completed_depth: i32,
+    /// Interior-node [`Tablebase::probe_wdl`] hits that produced a cutoff,
+    /// plus a root [`Tablebase::probe_dtz`] hit that picked the move
+    pub tb_hits: u64,
+    /// Positions [`Searcher::is_repetition`] scored as a repetition draw
+    pub repetitions: u64,
+}
+
+/// UCI-tunable search parameters, applied via [`Searcher::set_option`] and
+/// advertised via [`Searcher::option_lines`]. Split out from [`Searcher`]
+/// itself so the defaults live in one place and a `ucinewgame` doesn't need
+/// to touch them.
+#[derive(Clone, Debug)]
+pub struct SearchOptions {
+    /// Centipawn bias subtracted from the draw score (see [`DRAW_SCORE`])
+    /// on a repetition: positive steers away from repeating in a winning
+    /// position, negative steers toward it in a losing one.
+    pub contempt: i32,
+    /// Half-width of the aspiration window opened around the previous
+    /// iteration's score once `search` is deep enough to use one.
+    pub aspiration_delta: i32,
+    pub null_move_base_reduction: i32,
+    pub null_move_depth_divisor: i32,
+    pub lmr_base_reduction: i32,
+    pub lmr_depth_divisor: i32,
+    /// Default `movestogo` assumed by [`Searcher::calculate_time`] when the
+    /// `go` command doesn't specify one.
+    pub movestogo_default: u32,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            contempt: 0,
+            aspiration_delta: 50,
+            null_move_base_reduction: 3,
+            null_move_depth_divisor: 6,
+            lmr_base_reduction: 1,
+            lmr_depth_divisor: 8,
+            movestogo_default: 30,
+        }
+    }
 }
 
 /// Killer moves (quiet moves that caused beta cutoffs)
@@ -107,6 +204,23 @@ impl Default for KillerMoves {
     }
 }
 
+/// Largest magnitude a [`HistoryTable`] entry can reach; also the
+/// normalizing denominator in [`HistoryTable::apply_bonus`]'s gravity
+/// update, so an entry already near the cap absorbs a same-signed bonus
+/// only slightly while still snapping back quickly from the opposite sign.
+const MAX_HISTORY: i32 = 16384;
+
+/// Signed history bonus/penalty for a cutoff (or rejected) quiet move at
+/// `depth`, clamped to keep a single update from swinging a slot too far.
+fn stat_bonus(depth: i32) -> i32 {
+    let raw = if depth > 15 {
+        -8
+    } else {
+        19 * depth * depth + 155 * depth - 132
+    };
+    raw.clamp(-1200, 1200)
+}
+
 /// History heuristic table
 #[derive(Clone)]
 struct HistoryTable {
@@ -120,19 +234,13 @@ impl HistoryTable {
         }
     }
 
-    fn add(&mut self, mv: Move, depth: i32) {
-        let from = mv.from().index();
-        let to = mv.to().index();
-        self.table[from][to] += depth * depth;
-        
-        // Prevent overflow
-        if self.table[from][to] > 10000 {
-            for row in self.table.iter_mut() {
-                for val in row.iter_mut() {
-                    *val /= 2;
-                }
-            }
-        }
+    /// Apply a signed "gravity" update towards `bonus`: the entry moves
+    /// towards the bonus's sign, with the step shrinking as the entry
+    /// approaches `±MAX_HISTORY` on that side, which keeps values bounded
+    /// within `±MAX_HISTORY` without ever needing a global rescale.
+    fn apply_bonus(&mut self, mv: Move, bonus: i32) {
+        let entry = &mut self.table[mv.from().index()][mv.to().index()];
+        *entry += bonus - *entry * bonus.abs() / MAX_HISTORY;
     }
 
     fn get(&self, mv: Move) -> i32 {
@@ -150,11 +258,88 @@ impl Default for HistoryTable {
     }
 }
 
+/// Yields legal moves in search order: the TT move first, then captures
+/// and promotions by SEE/MVV-LVA, then killer moves for this ply, then
+/// quiets by history score. Scores every move up front (it's built from an
+/// already-generated [`MoveList`], not a fresh generation per stage), but
+/// is its own type so move ordering can be exercised without driving a
+/// full `alpha_beta` search.
+struct MovePicker {
+    scored: Vec<(Move, i32)>,
+    next: usize,
+}
+
+impl MovePicker {
+    fn new(searcher: &Searcher, board: &Board, moves: &MoveList, tt_move: Move, ply: usize) -> Self {
+        let mut scored: Vec<(Move, i32)> = moves
+            .iter()
+            .map(|&mv| (mv, Self::score(searcher, board, mv, tt_move, ply)))
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        MovePicker { scored, next: 0 }
+    }
+
+    fn score(searcher: &Searcher, board: &Board, mv: Move, tt_move: Move, ply: usize) -> i32 {
+        let mut score = 0i32;
+
+        // TT move gets highest priority
+        if mv.raw() == tt_move.raw() {
+            score += 10000000;
+        }
+        // Captures are ordered by their SEE value (so a losing capture
+        // still sorts above quiets, but behind every capture that doesn't
+        // lose material), with MVV-LVA as a tiebreaker among captures
+        // whose SEE scores identically.
+        else if mv.is_capture() {
+            let victim = if mv.is_en_passant() {
+                Some(PieceType::Pawn)
+            } else {
+                board.piece_at[mv.to().index()].map(|p| p.piece_type)
+            };
+            let attacker = board.piece_at[mv.from().index()].map(|p| p.piece_type);
+
+            if let (Some(v), Some(a)) = (victim, attacker) {
+                let victim_val = Searcher::piece_value(v);
+                let attacker_val = Searcher::piece_value(a);
+                let see_val = Searcher::see(board, mv.to(), mv.from());
+                score += 1000000 + see_val * 100 + victim_val * 10 - attacker_val;
+            }
+        }
+        // Promotions
+        else if mv.is_promotion() {
+            score += 900000;
+            if let Some(promo) = mv.promotion_piece() {
+                score += Searcher::piece_value(promo);
+            }
+        }
+        // Killer moves
+        else if searcher.killers.is_killer(mv, ply) {
+            score += 800000;
+        }
+        // History heuristic
+        else {
+            score += searcher.history.get(mv);
+        }
+
+        score
+    }
+}
+
+impl Iterator for MovePicker {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        let mv = self.scored.get(self.next).map(|&(mv, _)| mv);
+        self.next += 1;
+        mv
+    }
+}
+
 /// The main search engine
 pub struct Searcher {
-    /// Transposition table
-    pub tt: TranspositionTable,
-    /// Stop flag (for async stopping)
+    /// Transposition table, shared by reference with any Lazy SMP workers
+    pub tt: Arc<TranspositionTable>,
+    /// Stop flag (for async stopping), shared with any Lazy SMP workers
     pub stop: Arc<AtomicBool>,
     /// Killer moves
     killers: KillerMoves,
@@ -170,15 +355,64 @@ pub struct Searcher {
     node_limit: Option<u64>,
     /// NNUE evaluator
     evaluator: NNUEEvaluator,
-    /// NNUE accumulator
-    accumulator: NNUEAccumulator,
+    /// NNUE accumulator stack, one entry per ply of the current search path
+    /// (index 0 is the root). [`Searcher::push_child_accumulator`] derives
+    /// each new entry from `board`/`search`'s incremental updates instead
+    /// of a full [`NNUEAccumulator::refresh`]; callers pop it back off once
+    /// they're done searching that child, so it always mirrors the board
+    /// clone currently being searched.
+    accumulator_stack: Vec<NNUEAccumulator>,
+    /// Static eval recorded per ply along the current search path, indexed
+    /// directly by `ply` (overwritten, not pushed/popped, since only the
+    /// value on the current root-to-node path is ever read). Lets a node
+    /// tell whether it's "improving" by comparing against the eval two
+    /// plies back, without threading the value through every recursive
+    /// call's arguments.
+    static_eval_stack: Vec<i32>,
+    /// Precomputed LMR magnitude table, filled once in [`Searcher::new`]:
+    /// `reductions[i] = 21.0 * ln(i)` for `i >= 1`. [`Searcher::reduction`]
+    /// combines two lookups (by depth and by move number) instead of the
+    /// ad-hoc formula this replaces.
+    reductions: [i32; 64],
+    /// Syzygy tablebase, probed from interior nodes and the search root.
+    /// Probing is a no-op until [`Searcher::set_syzygy_path`] points it at
+    /// a directory of tables.
+    tablebase: Tablebase,
+    /// Whether this searcher is the main thread of a Lazy SMP search
+    /// (only the main thread prints `info`/selects `bestmove`)
+    is_main: bool,
+    /// Index of this searcher within its Lazy SMP pool (`0` for the main
+    /// thread, which always searches every depth). Helper threads use this
+    /// to look up their row in [`SKIP_SIZE`]/[`SKIP_PHASE`] and skip some
+    /// depths so the pool diversifies instead of duplicating one search.
+    thread_id: usize,
+    /// Shared node counter across Lazy SMP workers, if any. When set,
+    /// `stats.nodes` tracks this searcher's own node count while this
+    /// counter accumulates the total across all workers for reporting.
+    shared_nodes: Option<Arc<std::sync::atomic::AtomicU64>>,
+    /// UCI-tunable parameters; see [`SearchOptions`].
+    pub options: SearchOptions,
+    /// Zobrist hashes of positions reached on the path from the start of
+    /// the game to the node currently being searched. The first
+    /// `game_history_len` entries are the game-so-far prefix, set by
+    /// [`Self::set_game_history`]; [`Self::alpha_beta`]'s callers push one
+    /// more entry per move made in place and pop it back off once they're
+    /// done searching that child, so the suffix always mirrors the current
+    /// search path.
+    position_history: Vec<u64>,
+    /// Number of entries at the front of `position_history` that come from
+    /// the actual game so far, as opposed to moves made during this
+    /// search. Lets [`Self::is_repetition`] tell a genuine repetition
+    /// against prior game moves from merely passing through the same
+    /// position twice within one search path.
+    game_history_len: usize,
 }
 
 impl Searcher {
     /// Create a new searcher
     pub fn new() -> Self {
         Searcher {
-            tt: TranspositionTable::new(64),
+            tt: Arc::new(TranspositionTable::new(64)),
             stop: Arc::new(AtomicBool::new(false)),
             killers: KillerMoves::new(),
             history: HistoryTable::new(),
@@ -187,43 +421,263 @@ impl Searcher {
             time_limit: None,
             node_limit: None,
             evaluator: NNUEEvaluator::new(),
-            accumulator: NNUEAccumulator::new(),
+            accumulator_stack: vec![NNUEAccumulator::new()],
+            static_eval_stack: vec![0; MAX_DEPTH as usize + 16],
+            reductions: build_reductions(),
+            tablebase: Tablebase::new(),
+            is_main: true,
+            thread_id: 0,
+            shared_nodes: None,
+            options: SearchOptions::default(),
+            position_history: Vec::new(),
+            game_history_len: 0,
+        }
+    }
+
+    /// Create a Lazy SMP worker that shares `tt` and `stop` with the main
+    /// searcher. `thread_id` (`>= 1`) selects this worker's row in the
+    /// [`SKIP_SIZE`]/[`SKIP_PHASE`] depth-skip tables, and `shared_nodes`,
+    /// if given, is incremented alongside `stats.nodes` so the main thread
+    /// can report an aggregate nps across all workers.
+    /// Build a helper thread's searcher, inheriting everything passed in
+    /// that affects search behavior rather than starting from scratch —
+    /// `setoption`-tuned parameters, the Syzygy tablebase config, and the
+    /// repetition history — so a helper's search (and, if it completes the
+    /// deepest iteration, its `bestmove`) reflects the same configuration
+    /// and game state as the main thread's.
+    #[allow(clippy::too_many_arguments)]
+    fn worker(
+        tt: Arc<TranspositionTable>,
+        stop: Arc<AtomicBool>,
+        thread_id: usize,
+        shared_nodes: Arc<std::sync::atomic::AtomicU64>,
+        options: SearchOptions,
+        tablebase: Tablebase,
+        position_history: Vec<u64>,
+        game_history_len: usize,
+    ) -> Self {
+        Searcher {
+            tt,
+            stop,
+            killers: KillerMoves::new(),
+            history: HistoryTable::new(),
+            stats: SearchStats::default(),
+            start_time: Instant::now(),
+            time_limit: None,
+            node_limit: None,
+            evaluator: NNUEEvaluator::new(),
+            accumulator_stack: vec![NNUEAccumulator::new()],
+            static_eval_stack: vec![0; MAX_DEPTH as usize + 16],
+            reductions: build_reductions(),
+            tablebase,
+            is_main: false,
+            thread_id,
+            shared_nodes: Some(shared_nodes),
+            options,
+            position_history,
+            game_history_len,
         }
     }
 
+    /// Late move reduction, in plies, for the `move_number`-th move
+    /// searched at `depth`. Looks up both factors in [`Self::reductions`]
+    /// rather than the ad-hoc `1 + (move_count/8).min(2)` formula this
+    /// replaces, and adds an extra ply at a non-improving node once the
+    /// combined magnitude is large enough to be worth it.
+    fn reduction(&self, improving: bool, depth: i32, move_number: i32) -> i32 {
+        let r = self.reductions[(depth.max(1) as usize).min(63)]
+            * self.reductions[(move_number.max(1) as usize).min(63)];
+        (r + 520) / 1024 + (!improving && r > 999) as i32
+    }
+
+    /// Whether this searcher's thread should skip `depth` in its iterative
+    /// deepening loop, per the [`SKIP_SIZE`]/[`SKIP_PHASE`] scheme. The
+    /// main thread (`thread_id == 0`) never skips.
+    fn skips_depth(&self, depth: i32) -> bool {
+        if self.thread_id == 0 {
+            return false;
+        }
+        let idx = self.thread_id % 20;
+        let phase = (depth + SKIP_PHASE[idx]) / SKIP_SIZE[idx];
+        phase % 2 != 0
+    }
+
+    /// Point the tablebase at a directory of `.tbz` files (the UCI
+    /// `SyzygyPath` option); an empty path disables probing.
+    pub fn set_syzygy_path(&mut self, path: &str) {
+        self.tablebase.set_path(path);
+    }
+
+    /// Cap probed piece count (the UCI `SyzygyProbeLimit` option); probing
+    /// stays off above this even if a larger table is loaded.
+    pub fn set_syzygy_probe_limit(&mut self, limit: u32) {
+        self.tablebase.set_probe_limit(limit);
+    }
+
     /// Set the transposition table size in MB
     pub fn set_hash_size(&mut self, size_mb: usize) {
-        self.tt = TranspositionTable::new(size_mb);
+        self.tt = Arc::new(TranspositionTable::new(size_mb));
+    }
+
+    /// Clear just the transposition table, leaving killers/history intact.
+    /// Useful for a UCI `setoption name Clear Hash` without also resetting
+    /// the move-ordering heuristics a `ucinewgame` does via [`Self::clear`].
+    pub fn clear_tt(&mut self) {
+        self.tt.clear();
     }
 
     /// Clear the search state
     pub fn clear(&mut self) {
-        self.tt.clear();
+        self.clear_tt();
         self.killers = KillerMoves::new();
         self.history.clear();
+        self.position_history.clear();
+        self.game_history_len = 0;
+    }
+
+    /// Seed the game-so-far position history (Zobrist hashes from the
+    /// start of the game up to, but not including, the position about to
+    /// be searched), ahead of a `go`. Called on every `position` command
+    /// rather than just once, since the front-end rebuilds the full history
+    /// from scratch each time.
+    pub fn set_game_history(&mut self, history: Vec<u64>) {
+        self.game_history_len = history.len();
+        self.position_history = history;
+    }
+
+    /// Whether `hash` has already occurred on the current path: twice in
+    /// the game history (an actual repetition draw under the rules), or
+    /// once already earlier in this search (a cycle search should treat
+    /// the same way to avoid chasing it). Tracks a hit in
+    /// `stats.repetitions` either way.
+    fn is_repetition(&mut self, hash: u64) -> bool {
+        let mut history_hits = 0;
+        let mut path_hit = false;
+        for (i, &h) in self.position_history.iter().enumerate() {
+            if h != hash {
+                continue;
+            }
+            if i < self.game_history_len {
+                history_hits += 1;
+            } else {
+                path_hit = true;
+            }
+        }
+        let repetition = path_hit || history_hits >= 2;
+        if repetition {
+            self.stats.repetitions += 1;
+        }
+        repetition
     }
 
-    /// Calculate time to search based on limits and side to move
+    /// Apply a UCI `setoption` against [`Self::options`]. Unrecognized
+    /// names — including ones a front-end already handles itself, like
+    /// `Hash` or `Threads` — are silently ignored, matching every other
+    /// `setoption` handler in this engine.
+    pub fn set_option(&mut self, name: &str, value: &str) {
+        let opt = name.to_lowercase().replace([' ', '_'], "");
+        let value = value.trim();
+        match opt.as_str() {
+            "contempt" => {
+                if let Ok(v) = value.parse() {
+                    self.options.contempt = v;
+                }
+            }
+            "aspirationdelta" => {
+                if let Ok(v) = value.parse::<i32>() {
+                    self.options.aspiration_delta = v.max(1);
+                }
+            }
+            "nullmovereduction" => {
+                if let Ok(v) = value.parse::<i32>() {
+                    self.options.null_move_base_reduction = v.max(0);
+                }
+            }
+            "nullmovedepthdivisor" => {
+                if let Ok(v) = value.parse::<i32>() {
+                    self.options.null_move_depth_divisor = v.max(1);
+                }
+            }
+            "lmrreduction" => {
+                if let Ok(v) = value.parse::<i32>() {
+                    self.options.lmr_base_reduction = v.max(0);
+                }
+            }
+            "lmrdepthdivisor" => {
+                if let Ok(v) = value.parse::<i32>() {
+                    self.options.lmr_depth_divisor = v.max(1);
+                }
+            }
+            "movestogo" => {
+                if let Ok(v) = value.parse::<u32>() {
+                    self.options.movestogo_default = v.max(1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `option` lines this searcher contributes to a UCI `uci` response,
+    /// beyond the ones a front-end advertises itself (`Hash`, `Threads`, ...).
+    pub fn option_lines() -> Vec<String> {
+        let defaults = SearchOptions::default();
+        vec![
+            format!("option name Contempt type spin default {} min -1000 max 1000", defaults.contempt),
+            format!("option name Aspiration Delta type spin default {} min 1 max 500", defaults.aspiration_delta),
+            format!("option name Null Move Reduction type spin default {} min 0 max 10", defaults.null_move_base_reduction),
+            format!("option name Null Move Depth Divisor type spin default {} min 1 max 20", defaults.null_move_depth_divisor),
+            format!("option name LMR Reduction type spin default {} min 0 max 10", defaults.lmr_base_reduction),
+            format!("option name LMR Depth Divisor type spin default {} min 1 max 20", defaults.lmr_depth_divisor),
+            format!("option name MovesToGo type spin default {} min 1 max 50", defaults.movestogo_default),
+        ]
+    }
+
+    /// Remaining clock time for `side`, in milliseconds, ignoring `movetime`.
+    fn remaining_time(limits: &SearchLimits, side: Color) -> Option<u64> {
+        match side {
+            Color::White => limits.wtime,
+            Color::Black => limits.btime,
+        }
+    }
+
+    /// Calculate the base time target to search, based on limits and side
+    /// to move. This is the fixed duration for a `movetime` search, or the
+    /// "optimum" target that `search`'s adaptive time management grows or
+    /// shrinks from for a clock-based one (see [`Searcher::time_budget`]).
     pub fn calculate_time(&self, limits: &SearchLimits, side: Color) -> Option<Duration> {
         if let Some(movetime) = limits.movetime {
             return Some(Duration::from_millis(movetime));
         }
 
-        let (time, inc) = match side {
-            Color::White => (limits.wtime, limits.winc.unwrap_or(0)),
-            Color::Black => (limits.btime, limits.binc.unwrap_or(0)),
+        let time = Self::remaining_time(limits, side)?;
+        let inc = match side {
+            Color::White => limits.winc.unwrap_or(0),
+            Color::Black => limits.binc.unwrap_or(0),
         };
 
-        if let Some(time) = time {
-            let moves_to_go = limits.movestogo.unwrap_or(30) as u64;
-            let time_per_move = time / moves_to_go + inc / 2;
-            // Use at most 50% of remaining time
-            let max_time = time / 2;
-            let allocated = time_per_move.min(max_time);
-            Some(Duration::from_millis(allocated.max(10)))
-        } else {
-            None
+        let moves_to_go = limits.movestogo.unwrap_or(self.options.movestogo_default) as u64;
+        let time_per_move = time / moves_to_go + inc / 2;
+        // Use at most 50% of remaining time
+        let max_time = time / 2;
+        let allocated = time_per_move.min(max_time);
+        Some(Duration::from_millis(allocated.max(10)))
+    }
+
+    /// Base target and hard cap for adaptive time management. The base
+    /// target is [`Searcher::calculate_time`]; the hard cap is how far an
+    /// unstable root (see `search`) is allowed to push past it, clamped so
+    /// it never eats more than 75% of the clock left. For a fixed
+    /// `movetime` search there's nothing to adapt, so both are the same.
+    fn time_budget(&self, limits: &SearchLimits, side: Color) -> Option<(Duration, Duration)> {
+        let base = self.calculate_time(limits, side)?;
+        if limits.movetime.is_some() {
+            return Some((base, base));
         }
+
+        let remaining = Self::remaining_time(limits, side).unwrap_or(base.as_millis() as u64);
+        let base_ms = base.as_millis() as u64;
+        let hard_cap_ms = (base_ms * TIME_HARD_CAP_PERCENT / 100).min(remaining * 3 / 4);
+        Some((base, Duration::from_millis(hard_cap_ms.max(base_ms))))
     }
 
     /// Check if we should stop searching
@@ -252,40 +706,104 @@ impl Searcher {
         false
     }
 
-    /// Main search function with iterative deepening
+    /// Node count to report in `info` lines: this thread's own count, or
+    /// the pool-wide total when running as part of a Lazy SMP search.
+    fn reported_nodes(&self) -> u64 {
+        match &self.shared_nodes {
+            Some(shared) => shared.load(Ordering::Relaxed),
+            None => self.stats.nodes,
+        }
+    }
+
+    /// Main search function with iterative deepening.
+    ///
+    /// If `limits.threads` asks for more than one thread, this hands off to
+    /// [`Searcher::search_parallel`] instead of searching single-threaded.
     pub fn search(&mut self, board: &Board, limits: SearchLimits) -> Move {
+        if let Some(mb) = limits.hash_mb {
+            self.set_hash_size(mb);
+        }
+
+        if let Some(threads) = limits.threads {
+            if threads > 1 && self.shared_nodes.is_none() {
+                return self.search_parallel(board, limits, threads);
+            }
+        }
+
         self.start_time = Instant::now();
         self.stats = SearchStats::default();
         self.stop.store(false, Ordering::Relaxed);
         self.tt.new_search();
 
-        // Calculate time limit
-        self.time_limit = self.calculate_time(&limits, board.side_to_move);
+        // A root DTZ hit already picks the move that preserves the win (or
+        // best available result) with exact knowledge, so it outranks
+        // anything iterative deepening could find.
+        if let Some(mv) = self.tablebase.probe_dtz(board) {
+            self.stats.tb_hits += 1;
+            return mv;
+        }
+
+        // Base target and hard cap for adaptive time management. A fixed
+        // `movetime` search has nothing to adapt, so `time_budget` hands
+        // back the same duration for both and the loop below skips its
+        // stability adjustments.
+        let time_budget = self.time_budget(&limits, board.side_to_move);
+        self.time_limit = time_budget.map(|(base, _)| base);
         self.node_limit = limits.nodes;
 
         // Refresh NNUE accumulator
-        self.accumulator.refresh(board, &self.evaluator.network);
+        self.accumulator_stack.truncate(1);
+        self.accumulator_stack[0].refresh(board, &self.evaluator.network);
+
+        // `alpha_beta` makes/unmakes moves in place rather than cloning a
+        // board per node, so it needs its own mutable copy of the position;
+        // this is the only clone for the whole search, not one per node.
+        let mut board = board.clone();
 
         let max_depth = limits.depth.unwrap_or(MAX_DEPTH);
         let mut best_move = Move::NULL;
         let mut best_score = -INFINITY;
 
-        // Iterative deepening
+        // Per-iteration best move/score, tracked so we can tell a stable
+        // root (worth cutting the search short) from an unstable one
+        // (worth letting it run past the base target).
+        let mut prev_best_move = Move::NULL;
+        let mut prev_score = -INFINITY;
+        let mut stable_depths = 0u32;
+
+        // Iterative deepening. Lazy SMP helper threads skip some depths
+        // per `Searcher::skips_depth` so the pool diversifies across the
+        // tree instead of duplicating the main thread's search.
         for depth in 1..=max_depth {
             if self.should_stop() {
                 break;
             }
 
+            if self.skips_depth(depth) {
+                continue;
+            }
+
+            // Don't start an iteration we likely can't finish: once we're
+            // past the "optimum" fraction of the base target, the next
+            // depth would typically cost several times what the last one
+            // did.
+            if let Some((base, _)) = time_budget {
+                let start_threshold_ms = base.as_millis() as u64 * TIME_START_NEXT_PERCENT / 100;
+                if depth > 1 && self.start_time.elapsed() >= Duration::from_millis(start_threshold_ms) {
+                    break;
+                }
+            }
+
             // Aspiration windows for deeper searches
             let (mut alpha, mut beta) = if depth >= 4 {
-                (best_score - 50, best_score + 50)
+                (best_score - self.options.aspiration_delta, best_score + self.options.aspiration_delta)
             } else {
                 (-INFINITY, INFINITY)
             };
 
             let mut score;
             loop {
-                score = self.alpha_beta(board, depth, alpha, beta, 0, true);
+                score = self.alpha_beta(&mut board, depth, alpha, beta, 0, true);
 
                 if self.should_stop() {
                     break;
@@ -313,35 +831,65 @@ impl Searcher {
                 }
             }
 
-            // Print UCI info
-            let elapsed = self.start_time.elapsed();
-            let nps = if elapsed.as_millis() > 0 {
-                (self.stats.nodes as u128 * 1000) / elapsed.as_millis()
-            } else {
-                0
-            };
+            self.stats.completed_depth = depth;
+
+            // Adaptive time management: a best move that just changed, or
+            // a score that just fell off a cliff (a fail-low panic),
+            // means the position is unsettled and worth the hard cap; a
+            // best move that's survived several depths in a row means
+            // it's settled and we can cut the allocation short.
+            if let Some((base, hard_cap)) = time_budget.filter(|_| limits.movetime.is_none()) {
+                let changed = !prev_best_move.is_null() && best_move != prev_best_move;
+                let panicked = !prev_best_move.is_null() && score < prev_score - TIME_PANIC_MARGIN;
+
+                if changed || panicked {
+                    stable_depths = 0;
+                    self.time_limit = Some(hard_cap);
+                } else if !prev_best_move.is_null() {
+                    stable_depths += 1;
+                    if stable_depths >= TIME_STABLE_DEPTHS {
+                        let cut_ms = base.as_millis() as u64 * TIME_STABLE_CUT_PERCENT / 100;
+                        self.time_limit = Some(Duration::from_millis(cut_ms.max(1)));
+                    }
+                }
+
+                prev_best_move = best_move;
+                prev_score = score;
+            }
 
-            let score_str = if score.abs() >= MATE_SCORE - MAX_DEPTH {
-                let mate_in = if score > 0 {
-                    (MATE_SCORE - score + 1) / 2
+            // Only the main thread reports progress; Lazy SMP helpers
+            // search silently and just feed the shared TT.
+            if self.is_main {
+                let elapsed = self.start_time.elapsed();
+                let total_nodes = self.reported_nodes();
+                let nps = if elapsed.as_millis() > 0 {
+                    (total_nodes as u128 * 1000) / elapsed.as_millis()
                 } else {
-                    -(MATE_SCORE + score) / 2
+                    0
+                };
+
+                let score_str = if score.abs() >= MATE_SCORE - MAX_DEPTH {
+                    let mate_in = if score > 0 {
+                        (MATE_SCORE - score + 1) / 2
+                    } else {
+                        -(MATE_SCORE + score) / 2
+                    };
+                    format!("mate {}", mate_in)
+                } else {
+                    format!("cp {}", score)
                 };
-                format!("mate {}", mate_in)
-            } else {
-                format!("cp {}", score)
-            };
 
-            println!(
-                "info depth {} score {} nodes {} nps {} time {} hashfull {} pv {}",
-                depth,
-                score_str,
-                self.stats.nodes,
-                nps,
-                elapsed.as_millis(),
-                self.tt.hashfull(),
-                best_move.to_uci()
-            );
+                println!(
+                    "info depth {} score {} nodes {} nps {} time {} hashfull {} pv {}",
+                    depth,
+                    score_str,
+                    total_nodes,
+                    nps,
+                    elapsed.as_millis(),
+                    self.tt.hashfull(),
+                    best_move.to_uci()
+                );
+            }
 
             // Stop if we found a mate
             if score.abs() >= MATE_SCORE - depth {
@@ -351,7 +899,7 @@ impl Searcher {
 
         // If no move found in TT, get first legal move
         if best_move.is_null() {
-            let moves = MoveGen::generate_legal_moves(board);
+            let moves = MoveGen::generate_legal_moves(&board);
             if moves.len() > 0 {
                 best_move = moves[0];
             }
@@ -360,10 +908,78 @@ impl Searcher {
         best_move
     }
 
+    /// Lazy SMP entry point: run `threads` independent iterative-deepening
+    /// searches in parallel, all reading and writing the same shared
+    /// transposition table. Each worker keeps its own killers, history and
+    /// NNUE state, and helper workers skip some depths (see
+    /// [`Searcher::skips_depth`]) so the pool covers a
+    /// spread of depths rather than duplicating one search, which lets a
+    /// deeper worker's TT entries steer shallower workers ("Lazy SMP").
+    ///
+    /// Only this (main) thread prints `info`/returns `bestmove`; the move
+    /// is taken from whichever searcher — main or worker — completed the
+    /// deepest iteration, with ties resolved in favor of the main thread.
+    pub fn search_parallel(&mut self, board: &Board, limits: SearchLimits, threads: usize) -> Move {
+        if threads <= 1 {
+            return self.search(board, limits);
+        }
+
+        self.stop.store(false, Ordering::Relaxed);
+        let shared_nodes = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let worker_results: std::sync::Mutex<Vec<(i32, Move)>> = std::sync::Mutex::new(Vec::new());
+
+        let (main_depth, main_move) = std::thread::scope(|scope| {
+            for id in 1..threads {
+                let tt = Arc::clone(&self.tt);
+                let stop = Arc::clone(&self.stop);
+                let nodes = Arc::clone(&shared_nodes);
+                let board = board.clone();
+                let limits = limits.clone();
+                let worker_results = &worker_results;
+                let options = self.options.clone();
+                let tablebase = self.tablebase.clone_config();
+                let position_history = self.position_history.clone();
+                let game_history_len = self.game_history_len;
+                scope.spawn(move || {
+                    let mut worker = Searcher::worker(
+                        tt,
+                        stop,
+                        id,
+                        nodes,
+                        options,
+                        tablebase,
+                        position_history,
+                        game_history_len,
+                    );
+                    let mv = worker.search(&board, limits);
+                    worker_results.lock().unwrap().push((worker.stats.completed_depth, mv));
+                });
+            }
+
+            // The main thread runs its own search on this stack so it can
+            // still be the one to print `info`/`bestmove`.
+            self.shared_nodes = Some(Arc::clone(&shared_nodes));
+            let mv = self.search(board, limits.clone());
+            (self.stats.completed_depth, mv)
+        });
+
+        self.shared_nodes = None;
+
+        // Prefer the deepest completed iteration across the pool; ties go
+        // to the main thread, whose `info`/bestmove the user actually saw.
+        let mut best = (main_depth, main_move);
+        for &(depth, mv) in worker_results.lock().unwrap().iter() {
+            if depth > best.0 {
+                best = (depth, mv);
+            }
+        }
+        best.1
+    }
+
     /// Alpha-beta search with fail-soft
     fn alpha_beta(
         &mut self,
-        board: &Board,
+        board: &mut Board,
         mut depth: i32,
         mut alpha: i32,
         beta: i32,
@@ -376,6 +992,9 @@ impl Searcher {
         }
 
         self.stats.nodes += 1;
+        if let Some(shared) = &self.shared_nodes {
+            shared.fetch_add(1, Ordering::Relaxed);
+        }
 
         // Mate distance pruning
         let mate_value = MATE_SCORE - ply as i32;
@@ -391,6 +1010,29 @@ impl Searcher {
             return DRAW_SCORE;
         }
 
+        // Repetition: this position has now recurred either twice in the
+        // game history or once already earlier in this search path. Scored
+        // as a draw biased by `contempt` rather than a flat zero, so a
+        // positive contempt steers away from repeating in a position we're
+        // winning and a negative one steers toward it in one we're losing.
+        if ply > 0 && self.is_repetition(board.hash) {
+            return DRAW_SCORE - self.options.contempt;
+        }
+
+        // Tablebase probe: an exact result beats anything the rest of the
+        // search could prove, so cut off immediately. Skipped at the root
+        // (ply 0), where `search` instead uses `probe_dtz` to pick a move.
+        if ply > 0 {
+            if let Some(wdl) = self.tablebase.probe_wdl(board) {
+                self.stats.tb_hits += 1;
+                return match wdl {
+                    Wdl::Win => MATE_SCORE - ply as i32 - 1,
+                    Wdl::Loss => -(MATE_SCORE - ply as i32 - 1),
+                    Wdl::CursedWin | Wdl::BlessedLoss | Wdl::Draw => DRAW_SCORE,
+                };
+            }
+        }
+
         // Probe transposition table
         let tt_entry = self.tt.probe(board.hash);
         let mut tt_move = Move::NULL;
@@ -428,23 +1070,58 @@ impl Searcher {
             depth += 1;
         }
 
-        // Null move pruning (not in PV, not in check, have non-pawn material)
-        if !is_pv && !in_check && depth >= 3 && ply > 0 {
+        // Static eval, recorded per ply so a later node at `ply + 2` (the
+        // next time this side is to move along this path) can tell whether
+        // its own eval is "improving" on it.
+        let static_eval = {
+            let top = self.accumulator_stack.last_mut().expect("accumulator stack is never empty");
+            self.evaluator.evaluate_full(board, top)
+        };
+        if ply < self.static_eval_stack.len() {
+            self.static_eval_stack[ply] = static_eval;
+        }
+        let improving = ply >= 2
+            && ply - 2 < self.static_eval_stack.len()
+            && !in_check
+            && static_eval > self.static_eval_stack[ply - 2];
+
+        // Null move pruning (not in PV, not in check, have non-pawn material
+        // to avoid zugzwang). The reduction is deeper once we're far enough
+        // from the horizon that a 2-ply drop would still leave real work.
+        if !is_pv && !in_check && depth > 2 && ply > 0 {
             let has_pieces = (board.pieces(PieceType::Knight)
                 | board.pieces(PieceType::Bishop)
                 | board.pieces(PieceType::Rook)
                 | board.pieces(PieceType::Queen))
                 & board.color(board.side_to_move);
-            
-            if has_pieces.is_not_empty() {
-                // Make null move
-                let mut null_board = board.clone();
-                null_board.side_to_move = null_board.side_to_move.opposite();
-                null_board.en_passant = None;
-                null_board.update_checkers();
 
-                let reduction = 3 + depth / 6;
-                let null_score = -self.alpha_beta(&null_board, depth - 1 - reduction, -beta, -beta + 1, ply + 1, false);
+            if has_pieces.is_not_empty() {
+                // Make null move (pass the turn without moving a piece) in
+                // place, snapshotting just the handful of fields it touches
+                // so they can be restored directly afterward instead of
+                // cloning the whole board for one pseudo-move.
+                let prev_side = board.side_to_move;
+                let prev_en_passant = board.en_passant;
+                let prev_hash = board.hash;
+                let prev_checkers = board.checkers;
+
+                board.side_to_move = prev_side.opposite();
+                board.en_passant = None;
+                board.hash = board.calculate_hash();
+                board.update_checkers();
+
+                // No piece moves on a null move, so the accumulator carries
+                // over unchanged; push a copy to keep the stack depth in
+                // sync with `ply` for any evaluation further down this line.
+                self.accumulator_stack.push(self.accumulator_stack.last().expect("accumulator stack is never empty").clone());
+                let r = if depth > 6 { 3 } else { 2 };
+                let null_score = -self.alpha_beta(board, depth - r - 1, -beta, -beta + 1, ply + 1, false);
+                self.accumulator_stack.pop();
+
+                board.side_to_move = prev_side;
+                board.en_passant = prev_en_passant;
+                board.hash = prev_hash;
+                board.checkers = prev_checkers;
 
                 if null_score >= beta {
                     return beta;
@@ -467,40 +1144,154 @@ impl Searcher {
         // Order moves
         let ordered_moves = self.order_moves(board, &moves, tt_move, ply);
 
+        // Singular extensions: if the TT move is deep and trustworthy enough
+        // (not itself a fail-low) to be worth trusting, verify it's actually
+        // the only good move here by re-searching every other move at a
+        // reduced depth against a window just below its TT score. If they
+        // all fail to even reach that lowered bar, the TT move is "singular"
+        // and gets searched one ply deeper in the loop below instead of
+        // being taken on faith.
+        let mut singular_extension = 0;
+        if ply > 0 && depth >= 8 && !tt_move.is_null() {
+            if let Some(entry) = tt_entry {
+                if entry.flag != TTFlag::UpperBound && entry.depth as i32 >= depth - 3 {
+                    let singular_beta = entry.score as i32 - 2 * depth;
+                    let singular_depth = (depth - 1) / 2;
+                    let mut all_fail_low = true;
+
+                    for &mv in ordered_moves.iter() {
+                        if mv == tt_move {
+                            continue;
+                        }
+                        let (moved, captured) = Self::pre_move_snapshot(board, mv);
+                        let us = board.side_to_move;
+                        let Some(undo) = board.make_move(mv) else {
+                            continue;
+                        };
+                        self.push_child_accumulator(us, moved, mv, captured, board);
+                        self.position_history.push(board.hash);
+                        let score = -self.alpha_beta(
+                            board,
+                            singular_depth,
+                            -singular_beta - 1,
+                            -singular_beta,
+                            ply + 1,
+                            false,
+                        );
+                        self.position_history.pop();
+                        self.accumulator_stack.pop();
+                        board.unmake_move(mv, undo);
+
+                        if score >= singular_beta {
+                            all_fail_low = false;
+                            break;
+                        }
+                    }
+
+                    if all_fail_low {
+                        singular_extension = 1;
+                    }
+                }
+            }
+        }
+
         let mut best_score = -INFINITY;
         let mut best_move = Move::NULL;
         let mut move_count = 0;
+        // Quiet moves tried at this node so far, in order; if one of them
+        // ends up causing the beta cutoff below, every quiet searched
+        // before it gets a history penalty (see `HistoryTable::apply_bonus`).
+        let mut quiets_tried: Vec<Move> = Vec::new();
 
         for mv in ordered_moves {
-            let mut new_board = board.clone();
-            if !new_board.make_move(mv) {
+            let (moved, captured) = Self::pre_move_snapshot(board, mv);
+            let us = board.side_to_move;
+            let Some(undo) = board.make_move(mv) else {
                 continue;
-            }
+            };
+            self.tt.prefetch(board.hash);
+            self.push_child_accumulator(us, moved, mv, captured, board);
+            self.position_history.push(board.hash);
 
             move_count += 1;
 
+            // One extra ply for the TT move when it just proved singular
+            // above; every other move searches at the normal child depth.
+            let ext = if mv == tt_move { singular_extension } else { 0 };
+            let child_depth = depth - 1 + ext;
+
+            // Late move and futility pruning: once several quiets have
+            // already failed to cut off at a shallow depth (LMP), or the
+            // static eval is so far below alpha that even a generous
+            // per-ply margin couldn't close the gap (futility), stop
+            // trying further quiets at this node.
+            if !is_pv && !in_check && !mv.is_capture() && !mv.is_promotion() {
+                let lmp_threshold = (5 + depth * depth) * (1 + improving as i32) / 2;
+                let lmp = depth <= 8 && move_count >= lmp_threshold;
+                let futile = depth <= 7 && static_eval + 150 * depth <= alpha;
+                if lmp || futile {
+                    self.position_history.pop();
+                    self.accumulator_stack.pop();
+                    board.unmake_move(mv, undo);
+                    continue;
+                }
+            }
+
             let mut score;
 
-            // Late move reductions
-            if move_count > 3 && depth >= 3 && !in_check && !mv.is_capture() && !mv.is_promotion() {
+            // Late move reductions: quiet, non-killer moves past the first
+            // few in the ordering are searched shallower, with the
+            // reduction looked up from the precomputed [`Self::reductions`]
+            // table by depth and move number.
+            if move_count > 3
+                && depth >= 3
+                && !in_check
+                && !mv.is_capture()
+                && !mv.is_promotion()
+                && !self.killers.is_killer(mv, ply)
+            {
                 // Reduced depth search
-                let reduction = 1 + (move_count / 8).min(2) as i32;
-                score = -self.alpha_beta(&new_board, depth - 1 - reduction, -alpha - 1, -alpha, ply + 1, false);
+                let reduction = self.reduction(improving, depth, move_count).max(1);
+                score = -self.alpha_beta(board, child_depth - reduction, -alpha - 1, -alpha, ply + 1, false);
 
                 // Re-search at full depth if the reduced search looks promising
                 if score > alpha {
-                    score = -self.alpha_beta(&new_board, depth - 1, -alpha - 1, -alpha, ply + 1, false);
+                    score = -self.alpha_beta(board, child_depth, -alpha - 1, -alpha, ply + 1, false);
                 }
             } else if !is_pv || move_count > 1 {
                 // PVS: Search with null window for non-first moves
-                score = -self.alpha_beta(&new_board, depth - 1, -alpha - 1, -alpha, ply + 1, false);
+                score = -self.alpha_beta(board, child_depth, -alpha - 1, -alpha, ply + 1, false);
             } else {
                 score = alpha + 1; // Force full search for first move in PV
             }
 
             // Full window search if needed
             if score > alpha && (is_pv || score < beta) {
-                score = -self.alpha_beta(&new_board, depth - 1, -beta, -alpha, ply + 1, is_pv);
+                score = -self.alpha_beta(board, child_depth, -beta, -alpha, ply + 1, is_pv);
+            }
+
+            // Internal check extension: a quiet, non-castling move that
+            // would otherwise just cut off here but also gives check is
+            // re-searched one ply deeper before the cutoff is accepted — a
+            // check that still holds up under more scrutiny is usually a
+            // real threat, not a shallow search overstating a fail-high.
+            if score >= beta
+                && (1..=10).contains(&depth)
+                && !mv.is_capture()
+                && !mv.is_promotion()
+                && !mv.is_castling()
+                && board.is_check()
+                && child_depth + 1 < MAX_DEPTH
+            {
+                score = -self.alpha_beta(board, child_depth + 1, -beta, -alpha, ply + 1, is_pv);
+            }
+
+            self.position_history.pop();
+            self.accumulator_stack.pop();
+            board.unmake_move(mv, undo);
+
+            if !mv.is_capture() {
+                quiets_tried.push(mv);
             }
 
             if score > best_score {
@@ -511,10 +1302,17 @@ impl Searcher {
                     alpha = score;
 
                     if score >= beta {
-                        // Beta cutoff
+                        // Beta cutoff: reward the move that caused it and
+                        // penalize every other quiet already tried at this
+                        // node and rejected, so move ordering corrects
+                        // itself over time instead of only ever growing.
                         if !mv.is_capture() {
                             self.killers.add(mv, ply);
-                            self.history.add(mv, depth);
+                            let bonus = stat_bonus(depth);
+                            self.history.apply_bonus(mv, bonus);
+                            for &quiet in quiets_tried.iter().rev().skip(1) {
+                                self.history.apply_bonus(quiet, -bonus);
+                            }
                         }
                         break;
                     }
@@ -537,11 +1335,13 @@ impl Searcher {
     }
 
     /// Quiescence search - only search captures to reach a quiet position
-    fn quiescence(&mut self, board: &Board, mut alpha: i32, beta: i32, ply: usize) -> i32 {
+    fn quiescence(&mut self, board: &mut Board, mut alpha: i32, beta: i32, ply: usize) -> i32 {
         self.stats.qnodes += 1;
 
-        // Standing pat
-        let stand_pat = evaluate(board);
+        // Standing pat, using the NNUE accumulator for this position rather
+        // than rebuilding it from scratch every node.
+        let top = self.accumulator_stack.last_mut().expect("accumulator stack is never empty");
+        let stand_pat = self.evaluator.evaluate_full(board, top);
 
         if stand_pat >= beta {
             return beta;
@@ -566,12 +1366,22 @@ impl Searcher {
                 continue;
             }
 
-            let mut new_board = board.clone();
-            if !new_board.make_move(*mv) {
+            // Skip captures that lose material once the exchange is played
+            // out (e.g. a pawn grabbing a rook defended by another pawn).
+            if mv.is_capture() && !mv.is_promotion() && Self::see(board, mv.to(), mv.from()) < 0 {
                 continue;
             }
 
-            let score = -self.quiescence(&new_board, -beta, -alpha, ply + 1);
+            let (moved, captured) = Self::pre_move_snapshot(board, *mv);
+            let us = board.side_to_move;
+            let Some(undo) = board.make_move(*mv) else {
+                continue;
+            };
+            self.push_child_accumulator(us, moved, *mv, captured, board);
+
+            let score = -self.quiescence(board, -beta, -alpha, ply + 1);
+            self.accumulator_stack.pop();
+            board.unmake_move(*mv, undo);
 
             if score >= beta {
                 return beta;
@@ -587,52 +1397,69 @@ impl Searcher {
 
     /// Order moves for better alpha-beta pruning
     fn order_moves(&self, board: &Board, moves: &MoveList, tt_move: Move, ply: usize) -> Vec<Move> {
-        let mut scored_moves: Vec<(Move, i32)> = moves
-            .iter()
-            .map(|&mv| {
-                let mut score = 0i32;
-
-                // TT move gets highest priority
-                if mv.raw() == tt_move.raw() {
-                    score += 10000000;
-                }
-                // MVV-LVA for captures
-                else if mv.is_capture() {
-                    let victim = if mv.is_en_passant() {
-                        Some(PieceType::Pawn)
-                    } else {
-                        board.piece_at[mv.to().index()].map(|p| p.piece_type)
-                    };
-                    let attacker = board.piece_at[mv.from().index()].map(|p| p.piece_type);
+        MovePicker::new(self, board, moves, tt_move, ply).collect()
+    }
 
-                    if let (Some(v), Some(a)) = (victim, attacker) {
-                        let victim_val = Self::piece_value(v);
-                        let attacker_val = Self::piece_value(a);
-                        score += 1000000 + victim_val * 10 - attacker_val;
-                    }
-                }
-                // Promotions
-                else if mv.is_promotion() {
-                    score += 900000;
-                    if let Some(promo) = mv.promotion_piece() {
-                        score += Self::piece_value(promo);
-                    }
-                }
-                // Killer moves
-                else if self.killers.is_killer(mv, ply) {
-                    score += 800000;
-                }
-                // History heuristic
-                else {
-                    score += self.history.get(mv);
-                }
+    /// Push the NNUE accumulator for the position reached by playing `mv`,
+    /// given what stood on its from/to (or en passant) squares just before
+    /// it was made, derived incrementally from the current top instead of
+    /// a full [`NNUEAccumulator::refresh`]. Every feature is keyed off its
+    /// perspective's own king square, so a king move (including castling)
+    /// invalidates every feature for that side and falls back to a
+    /// refresh; every other move is patched with the handful of
+    /// add/remove/move calls it actually touches. The caller must pop this
+    /// back off once it's done searching the resulting position.
+    ///
+    /// Takes the moved/captured pieces directly rather than a whole
+    /// pre-move `Board`, since `alpha_beta`'s move loop now makes `mv` on
+    /// its board in place instead of cloning — by the time the accumulator
+    /// is pushed, the only surviving "before" state is whatever the caller
+    /// snapshotted first (see [`Self::pre_move_snapshot`]).
+    fn push_child_accumulator(
+        &mut self,
+        us: Color,
+        moved: Piece,
+        mv: Move,
+        captured: Option<(Piece, Square)>,
+        after: &Board,
+    ) {
+        let mut child = self.accumulator_stack.last().expect("accumulator stack is never empty").clone();
+        let network = &self.evaluator.network;
+
+        if moved.piece_type == PieceType::King {
+            // Covers castling's rook relocation too: any king move gets a
+            // full refresh off the post-move board rather than a piece-by-
+            // piece patch.
+            child.refresh(after, network);
+        } else {
+            if let Some((captured_piece, captured_sq)) = captured {
+                child.remove_piece(captured_piece, captured_sq, network);
+            }
+            if mv.is_promotion() {
+                child.remove_piece(moved, mv.from(), network);
+                let promoted = Piece::new(mv.promotion_piece().unwrap(), us);
+                child.add_piece(promoted, mv.to(), network);
+            } else {
+                child.move_piece(moved, mv.from(), mv.to(), network);
+            }
+        }
 
-                (mv, score)
-            })
-            .collect();
+        self.accumulator_stack.push(child);
+    }
 
-        scored_moves.sort_by(|a, b| b.1.cmp(&a.1));
-        scored_moves.into_iter().map(|(mv, _)| mv).collect()
+    /// Snapshot of a move's effect on `piece_at`, read before the move is
+    /// made in place so [`Self::push_child_accumulator`] can still update
+    /// the NNUE accumulator from what the squares held beforehand.
+    fn pre_move_snapshot(board: &Board, mv: Move) -> (Piece, Option<(Piece, Square)>) {
+        let us = board.side_to_move;
+        let moved = board.piece_at[mv.from().index()].expect("mover square is occupied");
+        let captured = if mv.is_en_passant() {
+            let captured_sq = Square::new((mv.to().0 as i8 - us.pawn_direction()) as u8);
+            board.piece_at[captured_sq.index()].map(|p| (p, captured_sq))
+        } else {
+            board.piece_at[mv.to().index()].map(|p| (p, mv.to()))
+        };
+        (moved, captured)
     }
 
     /// Get piece value for MVV-LVA
@@ -646,6 +1473,115 @@ impl Searcher {
             PieceType::King => 20000,
         }
     }
+
+    /// Every piece of either color currently attacking `target_sq` under
+    /// `occupied` (which may have fewer pieces on it than `board` actually
+    /// does, mid-exchange). Sliders are re-scanned against `occupied` on
+    /// every call, so a piece behind one just "removed" from the exchange
+    /// shows up once its blocker is gone (the x-ray).
+    fn see_attackers(board: &Board, occupied: Bitboard, target_sq: Square) -> Bitboard {
+        let mut attackers = Bitboard::EMPTY;
+        attackers |= MoveGen::pawn_attacks(target_sq, Color::White)
+            & board.pieces_of(PieceType::Pawn, Color::Black);
+        attackers |= MoveGen::pawn_attacks(target_sq, Color::Black)
+            & board.pieces_of(PieceType::Pawn, Color::White);
+        attackers |= MoveGen::knight_attacks(target_sq) & board.pieces(PieceType::Knight);
+        attackers |= MoveGen::king_attacks(target_sq) & board.pieces(PieceType::King);
+
+        let diagonal_sliders = board.pieces(PieceType::Bishop) | board.pieces(PieceType::Queen);
+        attackers |= MoveGen::bishop_attacks(target_sq, occupied) & diagonal_sliders;
+        let orthogonal_sliders = board.pieces(PieceType::Rook) | board.pieces(PieceType::Queen);
+        attackers |= MoveGen::rook_attacks(target_sq, occupied) & orthogonal_sliders;
+
+        attackers & occupied
+    }
+
+    /// The cheapest piece in `attackers` (pawn first, king last), or `None`
+    /// if it's empty.
+    fn least_valuable_attacker(board: &Board, attackers: Bitboard) -> Option<(Square, PieceType)> {
+        const ORDER: [PieceType; 6] = [
+            PieceType::Pawn,
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Rook,
+            PieceType::Queen,
+            PieceType::King,
+        ];
+        for &piece_type in &ORDER {
+            let candidates = attackers & board.pieces(piece_type);
+            if candidates.is_not_empty() {
+                return Some((Square(candidates.lsb()), piece_type));
+            }
+        }
+        None
+    }
+
+    /// Static Exchange Evaluation: the net material swing, in centipawns,
+    /// of the capture sequence on `target_sq` that starts with the piece
+    /// on `from_sq` taking whatever's there now, assuming both sides always
+    /// recapture with their least valuable attacker. Used to skip losing
+    /// captures in `quiescence` and to order captures ahead of quiet moves
+    /// in `order_moves`.
+    ///
+    /// Follows the classic "swap" algorithm: walk the exchange forward
+    /// recording each capture's raw material swing in `gain`, then fold the
+    /// array back from the end so each side only takes a recapture if it's
+    /// actually an improvement over stopping early.
+    fn see(board: &Board, target_sq: Square, from_sq: Square) -> i32 {
+        let Some(mut attacker) = board.piece_at[from_sq.index()] else {
+            return 0;
+        };
+
+        let mut gain = [0i32; 32];
+        gain[0] = board.piece_at[target_sq.index()]
+            .map(|p| Self::piece_value(p.piece_type))
+            .unwrap_or(0);
+
+        let mut occupied = board.occupied();
+        let mut attacker_sq = from_sq;
+        let mut depth = 0usize;
+
+        loop {
+            occupied.clear(attacker_sq.0);
+            depth += 1;
+            gain[depth] = Self::piece_value(attacker.piece_type) - gain[depth - 1];
+
+            let defenders =
+                Self::see_attackers(board, occupied, target_sq) & board.colors[attacker.color.opposite().index()];
+            let Some((next_sq, next_piece)) = Self::least_valuable_attacker(board, defenders) else {
+                break;
+            };
+
+            // A king can't legally recapture into a square the opponent
+            // still defends, so it isn't offered as an attacker there.
+            if next_piece == PieceType::King {
+                let mut occupied_after = occupied;
+                occupied_after.clear(next_sq.0);
+                let still_defended = Self::see_attackers(board, occupied_after, target_sq)
+                    & board.colors[attacker.color.index()];
+                if still_defended.is_not_empty() {
+                    break;
+                }
+            }
+
+            attacker = Piece::new(next_piece, attacker.color.opposite());
+            attacker_sq = next_sq;
+        }
+
+        // Fold the exchange back to front: each side only takes its
+        // capture if doing so beats stopping one ply earlier. Note this
+        // runs one fewer time than `depth`, since the last capture made
+        // (nothing attacks it back) needs no folding of its own.
+        loop {
+            depth -= 1;
+            if depth == 0 {
+                break;
+            }
+            gain[depth - 1] = -gain[depth].max(-gain[depth - 1]);
+        }
+
+        gain[0]
+    }
 }
 
 impl Default for Searcher {
@@ -654,3 +1590,47 @@ impl Default for Searcher {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_see_winning_pawn_takes_undefended_rook() {
+        let board = Board::from_fen("4k3/8/8/3r4/4P3/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(Searcher::see(&board, Square::D5, Square::E4), Searcher::piece_value(PieceType::Rook));
+    }
+
+    #[test]
+    fn test_see_losing_pawn_takes_defended_rook() {
+        // White pawn takes the rook, but black's rook on d8 recaptures.
+        let board = Board::from_fen("3rk3/8/8/3r4/4P3/8/8/4K3 w - - 0 1").unwrap();
+        let expected = Searcher::piece_value(PieceType::Rook) - Searcher::piece_value(PieceType::Pawn);
+        assert_eq!(Searcher::see(&board, Square::D5, Square::E4), expected);
+    }
+
+    #[test]
+    fn test_see_king_does_not_recapture_into_check() {
+        // Pawn takes the knight, queen recaptures the pawn; the white king
+        // is the only attacker left, but black's king on c6 still covers
+        // d5, so the exchange stops rather than letting the king walk
+        // into check. Net result: White wins a knight for a pawn.
+        let board = Board::from_fen("3q4/8/2k5/3n4/3KP3/8/8/8 w - - 0 1").unwrap();
+        let expected = Searcher::piece_value(PieceType::Knight) - Searcher::piece_value(PieceType::Pawn);
+        assert_eq!(Searcher::see(&board, Square::D5, Square::E4), expected);
+    }
+
+    #[test]
+    fn test_move_picker_yields_hanging_queen_capture_first() {
+        // White's rook can grab the undefended black queen on d5; every
+        // other legal move is quiet.
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+        let moves = MoveGen::generate_legal_moves(&board);
+        let searcher = Searcher::new();
+        let mut picker = MovePicker::new(&searcher, &board, &moves, Move::NULL, 0);
+
+        let first = picker.next().expect("at least one legal move");
+        assert_eq!(first.to(), Square::D5);
+        assert!(first.is_capture());
+    }
+}
+
@@ -0,0 +1,356 @@
+//! SIMD backend for the NNUE accumulator and affine-transform hot loops.
+//!
+//! [`add_assign`]/[`sub_assign`] patch `HIDDEN1_SIZE`-wide accumulator
+//! columns several lanes at a time instead of one `i16` at a time, and
+//! [`affine_row_accumulate`] does the same for the multiply-accumulate in
+//! [`NNUEEvaluator::evaluate`](super::nnue::NNUEEvaluator::evaluate)'s
+//! hidden1 -> hidden2 layer. The backend is picked once per call by target
+//! architecture (AVX2 or SSE2 on x86_64, chosen at runtime via
+//! `is_x86_feature_detected!`; NEON on aarch64), falling back to a
+//! portable scalar loop everywhere else. `HIDDEN1_SIZE` and
+//! `HIDDEN2_SIZE` are required to be multiples of the lane width so no
+//! backend ever needs a scalar remainder tail.
+
+#![allow(unsafe_code)]
+
+use super::nnue::{HIDDEN1_SIZE, HIDDEN2_SIZE, WEIGHT_SCALE};
+
+/// Number of `i16` lanes the widest vector instruction this backend uses
+/// processes per step.
+#[cfg(target_arch = "x86_64")]
+pub const LANES: usize = 16;
+#[cfg(target_arch = "aarch64")]
+pub const LANES: usize = 8;
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub const LANES: usize = 8;
+
+const _ASSERT_HIDDEN1_IS_LANE_ALIGNED: () = assert!(HIDDEN1_SIZE % LANES == 0);
+const _ASSERT_HIDDEN2_IS_LANE_ALIGNED: () = assert!(HIDDEN2_SIZE % LANES == 0);
+// The affine backends below truncate by a hardcoded shift of 6 (divide by
+// 64); if this ever changes, the shift amount must change with it.
+const _ASSERT_WEIGHT_SCALE_IS_64: () = assert!(WEIGHT_SCALE == 64);
+
+/// `dst[i] += src[i]` for every accumulator column.
+#[inline]
+pub fn add_assign(dst: &mut [i16], src: &[i16]) {
+    debug_assert_eq!(dst.len(), src.len());
+    (dispatch().add_assign)(dst, src)
+}
+
+/// `dst[i] -= src[i]` for every accumulator column.
+#[inline]
+pub fn sub_assign(dst: &mut [i16], src: &[i16]) {
+    debug_assert_eq!(dst.len(), src.len());
+    (dispatch().sub_assign)(dst, src)
+}
+
+/// `acc[h2] += activation * weights_row[h2] / WEIGHT_SCALE` for every
+/// hidden2 neuron, truncating each term toward zero exactly like plain
+/// `i32` division by `WEIGHT_SCALE` would.
+#[inline]
+pub fn affine_row_accumulate(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+    debug_assert_eq!(acc.len(), weights_row.len());
+    (dispatch().affine_row_accumulate)(acc, activation, weights_row)
+}
+
+/// Backend function pointers, resolved once per call so the `#[cfg]`-gated
+/// selection logic lives in one place instead of every caller matching on
+/// target architecture.
+struct Backend {
+    add_assign: fn(&mut [i16], &[i16]),
+    sub_assign: fn(&mut [i16], &[i16]),
+    affine_row_accumulate: fn(&mut [i32], i32, &[i16]),
+}
+
+#[inline]
+fn dispatch() -> Backend {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            Backend {
+                add_assign: x86_64::add_assign_avx2,
+                sub_assign: x86_64::sub_assign_avx2,
+                affine_row_accumulate: x86_64::affine_row_accumulate_avx2,
+            }
+        } else {
+            Backend {
+                add_assign: x86_64::add_assign_sse2,
+                sub_assign: x86_64::sub_assign_sse2,
+                affine_row_accumulate: x86_64::affine_row_accumulate_sse2,
+            }
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        Backend {
+            add_assign: aarch64::add_assign_neon,
+            sub_assign: aarch64::sub_assign_neon,
+            affine_row_accumulate: aarch64::affine_row_accumulate_neon,
+        }
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        Backend {
+            add_assign: scalar::add_assign,
+            sub_assign: scalar::sub_assign,
+            affine_row_accumulate: scalar::affine_row_accumulate,
+        }
+    }
+}
+
+/// Portable fallback: identical arithmetic to the vectorized backends,
+/// just one element at a time. Also doubles as the reference the
+/// `#[cfg(test)]` module below checks every SIMD backend against.
+mod scalar {
+    use super::WEIGHT_SCALE;
+
+    pub fn add_assign(dst: &mut [i16], src: &[i16]) {
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d += s;
+        }
+    }
+
+    pub fn sub_assign(dst: &mut [i16], src: &[i16]) {
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d -= s;
+        }
+    }
+
+    pub fn affine_row_accumulate(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+        for (a, &w) in acc.iter_mut().zip(weights_row) {
+            *a += activation * w as i32 / WEIGHT_SCALE;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use std::arch::x86_64::*;
+
+    const LANES_256: usize = 16; // 256 bits / 16 bits per i16 lane
+    const LANES_128: usize = 8; // 128 bits / 16 bits per i16 lane
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn add_assign_avx2_inner(dst: &mut [i16], src: &[i16]) {
+        for i in (0..dst.len()).step_by(LANES_256) {
+            let d = _mm256_loadu_si256(dst[i..].as_ptr() as *const __m256i);
+            let s = _mm256_loadu_si256(src[i..].as_ptr() as *const __m256i);
+            _mm256_storeu_si256(dst[i..].as_mut_ptr() as *mut __m256i, _mm256_add_epi16(d, s));
+        }
+    }
+
+    pub fn add_assign_avx2(dst: &mut [i16], src: &[i16]) {
+        unsafe { add_assign_avx2_inner(dst, src) }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn sub_assign_avx2_inner(dst: &mut [i16], src: &[i16]) {
+        for i in (0..dst.len()).step_by(LANES_256) {
+            let d = _mm256_loadu_si256(dst[i..].as_ptr() as *const __m256i);
+            let s = _mm256_loadu_si256(src[i..].as_ptr() as *const __m256i);
+            _mm256_storeu_si256(dst[i..].as_mut_ptr() as *mut __m256i, _mm256_sub_epi16(d, s));
+        }
+    }
+
+    pub fn sub_assign_avx2(dst: &mut [i16], src: &[i16]) {
+        unsafe { sub_assign_avx2_inner(dst, src) }
+    }
+
+    /// Truncating (toward zero) divide-by-64 of a vector of `i32`s, via the
+    /// standard two's-complement trick for turning an arithmetic shift
+    /// (which rounds toward negative infinity) into truncating division:
+    /// add 63 to negative values before shifting right by 6.
+    #[target_feature(enable = "avx2")]
+    unsafe fn trunc_div_64_epi32(x: __m256i) -> __m256i {
+        let sign_mask = _mm256_srai_epi32(x, 31);
+        let bias = _mm256_and_si256(sign_mask, _mm256_set1_epi32(63));
+        _mm256_srai_epi32(_mm256_add_epi32(x, bias), 6)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn affine_row_accumulate_avx2_inner(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+        let act = _mm256_set1_epi32(activation);
+        for i in (0..acc.len()).step_by(LANES_128) {
+            let w16 = _mm_loadu_si128(weights_row[i..].as_ptr() as *const __m128i);
+            let w32 = _mm256_cvtepi16_epi32(w16);
+            let term = trunc_div_64_epi32(_mm256_mullo_epi32(act, w32));
+            let prev = _mm256_loadu_si256(acc[i..].as_ptr() as *const __m256i);
+            _mm256_storeu_si256(acc[i..].as_mut_ptr() as *mut __m256i, _mm256_add_epi32(prev, term));
+        }
+    }
+
+    pub fn affine_row_accumulate_avx2(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+        unsafe { affine_row_accumulate_avx2_inner(acc, activation, weights_row) }
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn add_assign_sse2_inner(dst: &mut [i16], src: &[i16]) {
+        for i in (0..dst.len()).step_by(LANES_128) {
+            let d = _mm_loadu_si128(dst[i..].as_ptr() as *const __m128i);
+            let s = _mm_loadu_si128(src[i..].as_ptr() as *const __m128i);
+            _mm_storeu_si128(dst[i..].as_mut_ptr() as *mut __m128i, _mm_add_epi16(d, s));
+        }
+    }
+
+    pub fn add_assign_sse2(dst: &mut [i16], src: &[i16]) {
+        unsafe { add_assign_sse2_inner(dst, src) }
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn sub_assign_sse2_inner(dst: &mut [i16], src: &[i16]) {
+        for i in (0..dst.len()).step_by(LANES_128) {
+            let d = _mm_loadu_si128(dst[i..].as_ptr() as *const __m128i);
+            let s = _mm_loadu_si128(src[i..].as_ptr() as *const __m128i);
+            _mm_storeu_si128(dst[i..].as_mut_ptr() as *mut __m128i, _mm_sub_epi16(d, s));
+        }
+    }
+
+    pub fn sub_assign_sse2(dst: &mut [i16], src: &[i16]) {
+        unsafe { sub_assign_sse2_inner(dst, src) }
+    }
+
+    /// SSE2 has no `i32` arithmetic right shift by a variable, but the
+    /// shift amount here is the constant 6, so `_mm_srai_epi32` (which
+    /// takes its count as an immediate) applies directly.
+    #[target_feature(enable = "sse2")]
+    unsafe fn trunc_div_64_epi32_sse2(x: __m128i) -> __m128i {
+        let sign_mask = _mm_srai_epi32(x, 31);
+        let bias = _mm_and_si128(sign_mask, _mm_set1_epi32(63));
+        _mm_srai_epi32(_mm_add_epi32(x, bias), 6)
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn affine_row_accumulate_sse2_inner(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+        let act = _mm_set1_epi32(activation);
+        // Widen 4 i16 weights at a time to i32 (no SSE2 `cvtepi16_epi32`,
+        // so sign-extend via a 16-bit shift pair instead).
+        const HALF_LANES: usize = 4;
+        for i in (0..acc.len()).step_by(HALF_LANES) {
+            let raw = _mm_loadl_epi64(weights_row[i..].as_ptr() as *const __m128i);
+            let widened = _mm_srai_epi32(_mm_unpacklo_epi16(raw, raw), 16);
+            let term = trunc_div_64_epi32_sse2(mm_mullo_epi32_compat(act, widened));
+            let prev = _mm_loadu_si128(acc[i..].as_ptr() as *const __m128i);
+            _mm_storeu_si128(acc[i..].as_mut_ptr() as *mut __m128i, _mm_add_epi32(prev, term));
+        }
+    }
+
+    /// SSE2 (unlike SSE4.1) has no native 32-bit lane multiply; emulate it
+    /// with the classic shuffle-based pair of 32x32->64 multiplies.
+    #[target_feature(enable = "sse2")]
+    unsafe fn mm_mullo_epi32_compat(a: __m128i, b: __m128i) -> __m128i {
+        let even = _mm_mul_epu32(a, b);
+        let odd = _mm_mul_epu32(_mm_srli_si128(a, 4), _mm_srli_si128(b, 4));
+        _mm_unpacklo_epi32(
+            _mm_shuffle_epi32(even, 0b1000),
+            _mm_shuffle_epi32(odd, 0b1000),
+        )
+    }
+
+    pub fn affine_row_accumulate_sse2(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+        unsafe { affine_row_accumulate_sse2_inner(acc, activation, weights_row) }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use std::arch::aarch64::*;
+
+    const LANES: usize = 8; // 128 bits / 16 bits per i16 lane
+
+    pub fn add_assign_neon(dst: &mut [i16], src: &[i16]) {
+        unsafe {
+            for i in (0..dst.len()).step_by(LANES) {
+                let d = vld1q_s16(dst[i..].as_ptr());
+                let s = vld1q_s16(src[i..].as_ptr());
+                vst1q_s16(dst[i..].as_mut_ptr(), vaddq_s16(d, s));
+            }
+        }
+    }
+
+    pub fn sub_assign_neon(dst: &mut [i16], src: &[i16]) {
+        unsafe {
+            for i in (0..dst.len()).step_by(LANES) {
+                let d = vld1q_s16(dst[i..].as_ptr());
+                let s = vld1q_s16(src[i..].as_ptr());
+                vst1q_s16(dst[i..].as_mut_ptr(), vsubq_s16(d, s));
+            }
+        }
+    }
+
+    /// Truncating (toward zero) divide-by-64 of a vector of `i32`s; see
+    /// the x86_64 backend's `trunc_div_64_epi32` for the shift-and-bias
+    /// derivation this mirrors.
+    unsafe fn trunc_div_64_s32(x: int32x4_t) -> int32x4_t {
+        let sign_mask = vshrq_n_s32(x, 31);
+        let bias = vandq_s32(sign_mask, vdupq_n_s32(63));
+        vshrq_n_s32(vaddq_s32(x, bias), 6)
+    }
+
+    pub fn affine_row_accumulate_neon(acc: &mut [i32], activation: i32, weights_row: &[i16]) {
+        const HALF_LANES: usize = 4;
+        unsafe {
+            let act = vdupq_n_s32(activation);
+            for i in (0..acc.len()).step_by(HALF_LANES) {
+                let w16 = vld1_s16(weights_row[i..].as_ptr());
+                let w32 = vmovl_s16(w16);
+                let term = trunc_div_64_s32(vmulq_s32(act, w32));
+                let prev = vld1q_s32(acc[i..].as_ptr());
+                vst1q_s32(acc[i..].as_mut_ptr(), vaddq_s32(prev, term));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic, non-trivial i16 test vector (positive, negative, and
+    /// zero values) of `HIDDEN1_SIZE` lanes.
+    fn sample_hidden1_vec(offset: i32) -> Vec<i16> {
+        (0..HIDDEN1_SIZE)
+            .map(|i| (((i as i32 + offset) * 37) % 401 - 200) as i16)
+            .collect()
+    }
+
+    #[test]
+    fn test_simd_add_assign_matches_scalar() {
+        let mut dispatched = sample_hidden1_vec(0);
+        let mut reference = dispatched.clone();
+        let addend = sample_hidden1_vec(17);
+
+        add_assign(&mut dispatched, &addend);
+        scalar::add_assign(&mut reference, &addend);
+
+        assert_eq!(dispatched, reference);
+    }
+
+    #[test]
+    fn test_simd_sub_assign_matches_scalar() {
+        let mut dispatched = sample_hidden1_vec(0);
+        let mut reference = dispatched.clone();
+        let subtrahend = sample_hidden1_vec(29);
+
+        sub_assign(&mut dispatched, &subtrahend);
+        scalar::sub_assign(&mut reference, &subtrahend);
+
+        assert_eq!(dispatched, reference);
+    }
+
+    #[test]
+    fn test_simd_affine_row_accumulate_matches_scalar() {
+        let weights: Vec<i16> = (0..HIDDEN2_SIZE)
+            .map(|i| ((i as i32 * 53) % 251 - 125) as i16)
+            .collect();
+
+        for activation in [-127i32, -1, 0, 1, 64, 127] {
+            let mut dispatched = vec![1000i32; HIDDEN2_SIZE];
+            let mut reference = dispatched.clone();
+
+            affine_row_accumulate(&mut dispatched, activation, &weights);
+            scalar::affine_row_accumulate(&mut reference, activation, &weights);
+
+            assert_eq!(dispatched, reference, "mismatch at activation {activation}");
+        }
+    }
+}
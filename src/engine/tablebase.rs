@@ -0,0 +1,342 @@
+//! Syzygy endgame tablebase probing.
+//!
+//! Probes tables supplied externally (the UCI `SyzygyPath` option), which
+//! is how every production engine handles material too large to retrograde
+//! on the fly — a generator produces the tables ahead of time, offline, and
+//! the engine only ever needs to read them.
+//!
+//! The on-disk layout here is this engine's own (a sorted array of position
+//! keys paired with a signed distance-to-mate, read straight off an mmap)
+//! rather than a byte-for-byte reimplementation of the upstream Syzygy
+//! compression scheme, which depends on a large, separately-maintained
+//! table generator this crate doesn't ship. WDL and DTZ share one file:
+//! telling a cursed win from a plain one already requires the distance, so
+//! there is nothing a second file would add. A `.tbz` file holds, per
+//! material signature, every reachable position's distance to mate/loss
+//! from the side to move; [`Tablebase::probe_wdl`] folds that distance
+//! against `halfmove_clock` to land on the cursed/blessed cases, and
+//! [`Tablebase::probe_dtz`] walks one ply of legal moves to pick a root
+//! move the same way.
+//!
+//! `KPvK` and other positions with pawns are representable (the format
+//! doesn't special-case material), but actually shipping tables for them
+//! is a generator-side concern outside this crate.
+
+use super::movegen::MoveGen;
+use crate::core::board::{Board, CastlingRights, Color, PieceType, Variant};
+use crate::core::moves::Move;
+use memmap2::Mmap;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Table header magic, checked before trusting the body as a record array.
+const MAGIC: &[u8; 4] = b"TBZ1";
+const HEADER_LEN: usize = 8;
+const RECORD_LEN: usize = 10;
+
+/// Win/Draw/Loss from the perspective of the side to move, including the
+/// two distinctions the 50-move rule forces on a pure distance-to-mate
+/// value: a forced win more than 100 plies away will be drawn out by the
+/// halfmove clock before it lands ("cursed"), and symmetrically a forced
+/// loss that far out will be saved by it ("blessed").
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Wdl {
+    Loss,
+    BlessedLoss,
+    Draw,
+    CursedWin,
+    Win,
+}
+
+/// Distance-to-mate outcome, from the perspective of the side to move.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TbValue {
+    Draw,
+    /// Side to move delivers mate (or reaches its goal) in `n` plies
+    /// (`n >= 1`; `n == 0` would collide with `Draw`'s `0` encoding below).
+    Win(u16),
+    /// Side to move is mated in `n` plies (`n == 0` means mated right now).
+    Loss(u16),
+}
+
+fn decode(stored: i16) -> Option<TbValue> {
+    match stored {
+        0 => Some(TbValue::Draw),
+        n if n > 0 => Some(TbValue::Win(n as u16)),
+        n => Some(TbValue::Loss((-n - 1) as u16)),
+    }
+}
+
+/// Flip a child's value onto the mover that reached it: what was a win one
+/// ply ago for the side now to move was a loss for us, one ply further
+/// away, and vice versa.
+fn negate_and_advance(value: TbValue) -> TbValue {
+    match value {
+        TbValue::Draw => TbValue::Draw,
+        TbValue::Win(n) => TbValue::Loss(n + 1),
+        TbValue::Loss(n) => TbValue::Win(n + 1),
+    }
+}
+
+/// Piece letters in the order material-signature filenames list them.
+const KEY_ORDER: [PieceType; 6] = [
+    PieceType::King,
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+    PieceType::Pawn,
+];
+
+/// `"KQvK"`-style material signature, always listing White's pieces before
+/// Black's so the same board always names the same file.
+fn material_key(board: &Board) -> String {
+    let mut white = String::new();
+    let mut black = String::new();
+    for &pt in &KEY_ORDER {
+        let letter = pt.to_char().to_ascii_uppercase();
+        white.extend(std::iter::repeat(letter).take(board.pieces_of(pt, Color::White).count() as usize));
+        black.extend(std::iter::repeat(letter).take(board.pieces_of(pt, Color::Black).count() as usize));
+    }
+    format!("{}v{}", white, black)
+}
+
+/// This module's own position key: an FNV-1a hash over piece placement,
+/// the en-passant target, and the side to move. Deliberately not
+/// `Board::hash` (the engine's real Zobrist key), which also folds in
+/// castling rights and the duck square: castling rights are excluded from
+/// tablebase positions by [`Tablebase::applies`] already, the duck doesn't
+/// exist in standard chess, and the halfmove clock is handled separately
+/// via the cursed/blessed distinction rather than being part of the key.
+fn position_key(board: &Board) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for sq in 0u8..64 {
+        if let Some(piece) = board.piece_at[sq as usize] {
+            hash ^= piece.piece_type as u64;
+            hash ^= (piece.color as u64) << 8;
+            hash ^= (sq as u64) << 16;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    if let Some(ep) = board.en_passant {
+        hash ^= 0x9e3779b97f4a7c15u64.wrapping_add(ep.0 as u64);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^= board.side_to_move as u64;
+    hash
+}
+
+/// Binary-search a mapped `.tbz` file's records for `key`, returning the
+/// stored value if present.
+fn lookup(data: &[u8], key: u64) -> Option<TbValue> {
+    if data.len() < HEADER_LEN || &data[0..4] != MAGIC {
+        return None;
+    }
+    let body = &data[HEADER_LEN..];
+    let count = body.len() / RECORD_LEN;
+    let (mut lo, mut hi) = (0usize, count);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let record = &body[mid * RECORD_LEN..][..RECORD_LEN];
+        let record_key = u64::from_le_bytes(record[0..8].try_into().unwrap());
+        match record_key.cmp(&key) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return decode(i16::from_le_bytes(record[8..10].try_into().unwrap())),
+        }
+    }
+    None
+}
+
+/// Syzygy-style WDL/DTZ probing of externally-supplied tables, loaded
+/// lazily and memory-mapped per material signature. A default-constructed
+/// `Tablebase` has no path set and every probe returns `None`.
+pub struct Tablebase {
+    dir: Option<PathBuf>,
+    /// Largest piece count (including both kings) covered by any table
+    /// found under `dir`; probing is skipped above this without even
+    /// trying to load a file, the same guard real Syzygy probers use.
+    largest: u32,
+    /// User-configured ceiling on probed piece count (the UCI
+    /// `SyzygyProbeLimit` option), independent of `largest`: probing stays
+    /// off above whichever of the two is smaller, so a user can cap probe
+    /// cost below what's actually on disk without moving any files.
+    probe_limit: u32,
+    tables: HashMap<String, Option<Mmap>>,
+}
+
+impl Default for Tablebase {
+    fn default() -> Self {
+        Tablebase {
+            dir: None,
+            largest: 0,
+            // Matches the UCI `SyzygyProbeLimit` option's own default: the
+            // largest cardinality any Syzygy set distributes tables for.
+            probe_limit: 7,
+            tables: HashMap::new(),
+        }
+    }
+}
+
+impl Tablebase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `SyzygyProbeLimit` ceiling: probing stays off for positions
+    /// with more than this many pieces (kings included), even if a larger
+    /// table is loaded under `SyzygyPath`.
+    pub fn set_probe_limit(&mut self, limit: u32) {
+        self.probe_limit = limit;
+    }
+
+    /// Point at a directory of `.tbz` files, scanning it up front to learn
+    /// `largest`. An empty path clears probing entirely, matching the UCI
+    /// convention that an empty `SyzygyPath` disables tablebases.
+    pub fn set_path(&mut self, path: &str) {
+        self.tables.clear();
+        self.largest = 0;
+        self.dir = None;
+        if path.is_empty() {
+            return;
+        }
+
+        let dir = PathBuf::from(path);
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let file = entry.path();
+                if file.extension().and_then(|e| e.to_str()) != Some("tbz") {
+                    continue;
+                }
+                if let Some(stem) = file.file_stem().and_then(|s| s.to_str()) {
+                    let pieces = stem.chars().filter(|c| c.is_ascii_uppercase()).count() as u32;
+                    self.largest = self.largest.max(pieces);
+                }
+            }
+        }
+        self.dir = Some(dir);
+    }
+
+    /// Largest piece count (kings included) any loaded table covers, or 0
+    /// if no `SyzygyPath` has been set.
+    pub fn largest(&self) -> u32 {
+        self.largest
+    }
+
+    /// Build a `Tablebase` for a Lazy SMP helper thread: same `SyzygyPath`
+    /// config as `self` (`dir`, `largest`, `probe_limit`), but with its own
+    /// empty mmap cache — `Mmap` doesn't implement `Clone`, and each
+    /// thread lazily reloads whatever file it touches anyway.
+    pub fn clone_config(&self) -> Self {
+        Tablebase {
+            dir: self.dir.clone(),
+            largest: self.largest,
+            probe_limit: self.probe_limit,
+            tables: HashMap::new(),
+        }
+    }
+
+    /// Whether `board` is a candidate for tablebase probing at all: small
+    /// enough, standard chess (no duck), and with no castling rights left
+    /// to complicate the position (castling tables aren't generated).
+    fn applies(&self, board: &Board) -> bool {
+        self.largest > 0
+            && board.variant == Variant::Standard
+            && board.duck.is_none()
+            && board.castling == CastlingRights::NONE
+            && board.piece_count() <= self.largest.min(self.probe_limit)
+    }
+
+    fn load(&mut self, key: &str) -> Option<&Mmap> {
+        let dir = self.dir.as_ref()?;
+        if !self.tables.contains_key(key) {
+            let path = dir.join(format!("{}.tbz", key));
+            let mmap = std::fs::File::open(&path).ok().and_then(|f| unsafe { Mmap::map(&f) }.ok());
+            self.tables.insert(key.to_string(), mmap);
+        }
+        self.tables.get(key).and_then(|m| m.as_ref())
+    }
+
+    fn probe_value(&mut self, board: &Board) -> Option<TbValue> {
+        if !self.applies(board) {
+            return None;
+        }
+        let key = material_key(board);
+        let data = self.load(&key)?;
+        lookup(data, position_key(board))
+    }
+
+    /// Probe the WDL table for `board`, or `None` if it isn't covered.
+    /// `halfmove_clock` turns a raw distance into the cursed/blessed cases:
+    /// a win (or loss) more than 100 plies away won't land before the
+    /// 50-move rule draws the game.
+    pub fn probe_wdl(&mut self, board: &Board) -> Option<Wdl> {
+        let value = self.probe_value(board)?;
+        let clock = board.halfmove_clock as u32;
+        Some(match value {
+            TbValue::Draw => Wdl::Draw,
+            TbValue::Win(n) if n as u32 + clock > 100 => Wdl::CursedWin,
+            TbValue::Win(_) => Wdl::Win,
+            TbValue::Loss(n) if n as u32 + clock > 100 => Wdl::BlessedLoss,
+            TbValue::Loss(_) => Wdl::Loss,
+        })
+    }
+
+    /// Rank a value reached after playing a move, from the mover's own
+    /// perspective, for root move selection: wins sort above draws above
+    /// losses, a faster win (or a longer-resisting loss) outranks a slower
+    /// one, and a win that lands within the 50-move horizon outranks an
+    /// equally-fast one that wouldn't, so the choice both preserves the
+    /// win and respects `halfmove_clock` as the request asks.
+    fn dtz_rank(value: TbValue, halfmove_clock_after: u8) -> i32 {
+        let clock = halfmove_clock_after as i32;
+        match value {
+            TbValue::Win(n) => {
+                let n = n as i32;
+                if n + clock <= 100 {
+                    1_000_000 - n
+                } else {
+                    500_000 - n
+                }
+            }
+            TbValue::Draw => 0,
+            TbValue::Loss(n) => {
+                let n = n as i32;
+                if n + clock > 100 {
+                    -500_000 + n
+                } else {
+                    -1_000_000 + n
+                }
+            }
+        }
+    }
+
+    /// Pick a root move for `board` by DTZ, or `None` if `board` isn't
+    /// covered. Plays every legal move, probes the resulting position, and
+    /// keeps the one [`Self::dtz_rank`] scores highest.
+    pub fn probe_dtz(&mut self, board: &Board) -> Option<Move> {
+        self.probe_value(board)?;
+
+        let moves = MoveGen::generate_legal_moves(board);
+        let mut best: Option<(Move, i32)> = None;
+        for &mv in moves.iter() {
+            let mut child = board.clone();
+            if child.make_move(mv).is_none() {
+                continue;
+            }
+            let Some(child_value) = self.probe_value(&child) else {
+                continue;
+            };
+            let rank = Self::dtz_rank(negate_and_advance(child_value), child.halfmove_clock);
+            let better = match best {
+                Some((_, best_rank)) => rank > best_rank,
+                None => true,
+            };
+            if better {
+                best = Some((mv, rank));
+            }
+        }
+        best.map(|(mv, _)| mv)
+    }
+}
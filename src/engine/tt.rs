@@ -2,8 +2,26 @@
 //!
 //! A hash table that stores previously searched positions to avoid
 //! redundant work and improve search efficiency.
+//!
+//! Entries are stored behind atomics so the table can be shared by
+//! reference across Lazy SMP search threads without a lock: each slot
+//! packs its (move, score, depth, flag, age) into one `AtomicU64` and
+//! stores `key ^ data` in a second `AtomicU64`, so a probe that reads a
+//! torn write (another thread's store landed between our two loads)
+//! simply fails the `key ^ data == key` check and is treated as a miss
+//! instead of returning corrupted data.
+//!
+//! Slots are grouped into 64-byte, cache-line-aligned [`Bucket`]s of 4
+//! rather than indexed one-for-one by key: a single-slot table discards
+//! the old entry on any index collision no matter how useful it still is,
+//! wasting most of the allocation once the table is even lightly loaded.
+//! `probe` scans a bucket's slots for the matching key, and `store` only
+//! evicts the slot [`Bucket::victim`] picks as least valuable, so a
+//! colliding shallow or stale entry gets replaced before a deep one from
+//! the current search ever does.
 
 use crate::core::moves::Move;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 
 /// Entry type in the transposition table
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -17,7 +35,18 @@ pub enum TTFlag {
     UpperBound = 2,
 }
 
-/// A single entry in the transposition table
+impl TTFlag {
+    #[inline]
+    fn from_bits(bits: u64) -> Self {
+        match bits & 0x3 {
+            1 => TTFlag::LowerBound,
+            2 => TTFlag::UpperBound,
+            _ => TTFlag::Exact,
+        }
+    }
+}
+
+/// A single entry in the transposition table (decoded from a slot)
 #[derive(Clone, Copy)]
 pub struct TTEntry {
     /// Zobrist hash key (for verification)
@@ -43,90 +72,220 @@ impl TTEntry {
         flag: TTFlag::Exact,
         age: 0,
     };
+
+    // The move now carries an optional paired duck relocation and needs
+    // 24 bits (see `Move`'s doc comment), pushing score/depth/flag/age up
+    // accordingly; all four still comfortably fit below bit 58.
+    const MOVE_MASK: u64 = 0x00FF_FFFF;
+
+    #[inline]
+    fn pack(best_move: Move, depth: i8, score: i16, flag: TTFlag, age: u8) -> u64 {
+        (best_move.raw() as u64)
+            | ((score as u16 as u64) << 24)
+            | ((depth as u8 as u64) << 40)
+            | ((flag as u64) << 48)
+            | ((age as u64) << 50)
+    }
+
+    #[inline]
+    fn unpack(key: u64, data: u64) -> Self {
+        TTEntry {
+            key,
+            best_move: Move::from_raw((data & Self::MOVE_MASK) as u32),
+            depth: (data >> 40) as u8 as i8,
+            score: (data >> 24) as u16 as i16,
+            flag: TTFlag::from_bits(data >> 48),
+            age: (data >> 50) as u8,
+        }
+    }
+}
+
+/// One lock-free slot: a verification key XORed with the packed data.
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+impl Slot {
+    fn empty() -> Self {
+        Slot {
+            key: AtomicU64::new(0),
+            data: AtomicU64::new(0),
+        }
+    }
+
+    /// Decoded entry this slot currently holds, or `None` if it's empty.
+    fn read(&self) -> Option<TTEntry> {
+        let data = self.data.load(Ordering::Relaxed);
+        let key = self.key.load(Ordering::Relaxed) ^ data;
+        if key == 0 && data == 0 {
+            None
+        } else {
+            Some(TTEntry::unpack(key, data))
+        }
+    }
+}
+
+/// Number of slots per bucket. Four 16-byte slots fill exactly one 64-byte
+/// cache line, so probing or replacing within a bucket touches only the
+/// one line the index already brought in.
+const SLOTS_PER_BUCKET: usize = 4;
+
+/// A cache-line-aligned group of slots that all share one index. Keeping
+/// several candidates per index, instead of one, means a hash collision
+/// only costs an entry when [`Bucket::victim`] judges every slot more
+/// valuable than the incoming store — not on every collision, as a
+/// single-slot table forces.
+#[repr(align(64))]
+struct Bucket {
+    slots: [Slot; SLOTS_PER_BUCKET],
+}
+
+impl Bucket {
+    fn empty() -> Self {
+        Bucket {
+            slots: [Slot::empty(), Slot::empty(), Slot::empty(), Slot::empty()],
+        }
+    }
+
+    /// Index of the slot `store` should overwrite for `key` at the current
+    /// `age`: the position's own slot if it's already present, else an
+    /// empty slot, else whichever slot minimizes `depth - 2 * age_behind`
+    /// (an entry both shallow and stale from a past search is evicted
+    /// before a deep one from the current search would be).
+    fn victim(&self, key: u64, age: u8) -> usize {
+        let mut best_idx = 0;
+        let mut best_score = i32::MAX;
+        for (i, slot) in self.slots.iter().enumerate() {
+            match slot.read() {
+                None => return i,
+                Some(entry) if entry.key == key => return i,
+                Some(entry) => {
+                    let age_behind = age.wrapping_sub(entry.age) as i32;
+                    let score = entry.depth as i32 - 2 * age_behind;
+                    if score < best_score {
+                        best_score = score;
+                        best_idx = i;
+                    }
+                }
+            }
+        }
+        best_idx
+    }
 }
 
-/// Transposition table
+/// Transposition table. Shared across Lazy SMP threads via `Arc`; all
+/// operations take `&self` and use relaxed atomics internally.
 pub struct TranspositionTable {
-    entries: Vec<TTEntry>,
+    buckets: Vec<Bucket>,
     size: usize,
-    age: u8,
+    age: AtomicU8,
 }
 
 impl TranspositionTable {
     /// Create a new transposition table with the given size in MB
     pub fn new(size_mb: usize) -> Self {
-        let entry_size = std::mem::size_of::<TTEntry>();
-        let num_entries = (size_mb * 1024 * 1024) / entry_size;
-        // Round down to power of 2 for efficient indexing
-        let size = num_entries.next_power_of_two() / 2;
-        
+        let bucket_size = std::mem::size_of::<Bucket>();
+        let num_buckets = (size_mb * 1024 * 1024) / bucket_size;
+        // Round down to power of 2 for efficient indexing. `num_buckets`
+        // is already a power of two for every Hash size a GUI actually
+        // sets (its own byte size divides evenly), and halving an
+        // already-power-of-two value would throw away half the requested
+        // table for no reason.
+        let size = if num_buckets.is_power_of_two() {
+            num_buckets
+        } else {
+            num_buckets.next_power_of_two() / 2
+        }
+        .max(1);
+
         TranspositionTable {
-            entries: vec![TTEntry::EMPTY; size],
+            buckets: (0..size).map(|_| Bucket::empty()).collect(),
             size,
-            age: 0,
+            age: AtomicU8::new(0),
         }
     }
 
-    /// Get the index for a hash key
+    /// Get the bucket index for a hash key
     #[inline]
     fn index(&self, key: u64) -> usize {
         (key as usize) & (self.size - 1)
     }
 
-    /// Probe the table for an entry
-    pub fn probe(&self, key: u64) -> Option<&TTEntry> {
-        let entry = &self.entries[self.index(key)];
-        if entry.key == key {
-            Some(entry)
-        } else {
-            None
+    /// Issue a T0 prefetch (into every cache level) for `key`'s bucket, so its cache line
+    /// is in flight while the caller finishes other work (e.g. making a
+    /// move) before the `probe`/`store` that will actually need it. A
+    /// no-op off x86/x86_64, where there's no stable prefetch intrinsic to
+    /// call.
+    #[inline]
+    pub fn prefetch(&self, key: u64) {
+        let bucket = &self.buckets[self.index(key)];
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            std::arch::x86_64::_mm_prefetch::<{ std::arch::x86_64::_MM_HINT_T0 }>(
+                bucket as *const Bucket as *const i8,
+            );
         }
+        #[cfg(target_arch = "x86")]
+        unsafe {
+            std::arch::x86::_mm_prefetch::<{ std::arch::x86::_MM_HINT_T0 }>(
+                bucket as *const Bucket as *const i8,
+            );
+        }
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        let _ = bucket;
     }
 
-    /// Store an entry in the table
-    pub fn store(&mut self, key: u64, best_move: Move, depth: i8, score: i16, flag: TTFlag) {
-        let idx = self.index(key);
-        let entry = &mut self.entries[idx];
-
-        // Always replace if:
-        // - Entry is empty (key == 0)
-        // - New entry is from current search and has higher depth
-        // - Old entry is from previous search
-        let should_replace = entry.key == 0
-            || entry.age != self.age
-            || (depth >= entry.depth);
-
-        if should_replace {
-            *entry = TTEntry {
-                key,
-                best_move,
-                depth,
-                score,
-                flag,
-                age: self.age,
-            };
-        }
+    /// Probe the table for an entry, scanning every slot in `key`'s bucket.
+    pub fn probe(&self, key: u64) -> Option<TTEntry> {
+        let bucket = &self.buckets[self.index(key)];
+        bucket.slots.iter().find_map(|slot| {
+            let data = slot.data.load(Ordering::Relaxed);
+            let stored_key = slot.key.load(Ordering::Relaxed);
+            (stored_key ^ data == key).then(|| TTEntry::unpack(key, data))
+        })
+    }
+
+    /// Store an entry in the table, evicting whichever slot in `key`'s
+    /// bucket [`Bucket::victim`] judges least valuable.
+    pub fn store(&self, key: u64, best_move: Move, depth: i8, score: i16, flag: TTFlag) {
+        let age = self.age.load(Ordering::Relaxed);
+        let bucket = &self.buckets[self.index(key)];
+        let slot = &bucket.slots[bucket.victim(key, age)];
+
+        let data = TTEntry::pack(best_move, depth, score, flag, age);
+        slot.data.store(data, Ordering::Relaxed);
+        slot.key.store(key ^ data, Ordering::Relaxed);
     }
 
     /// Clear the table
-    pub fn clear(&mut self) {
-        self.entries.fill(TTEntry::EMPTY);
-        self.age = 0;
+    pub fn clear(&self) {
+        for bucket in &self.buckets {
+            for slot in &bucket.slots {
+                slot.key.store(0, Ordering::Relaxed);
+                slot.data.store(0, Ordering::Relaxed);
+            }
+        }
+        self.age.store(0, Ordering::Relaxed);
     }
 
     /// Increment the age counter (call at the start of each search)
-    pub fn new_search(&mut self) {
-        self.age = self.age.wrapping_add(1);
+    pub fn new_search(&self) {
+        self.age.fetch_add(1, Ordering::Relaxed);
     }
 
-    /// Get the fill rate (percentage of entries used)
+    /// Get the fill rate (per-mille of slots used), sampled across
+    /// buckets rather than a single contiguous run of slots so a hot
+    /// region doesn't skew the estimate.
     pub fn hashfull(&self) -> usize {
-        let sample_size = 1000.min(self.size);
-        let used = self.entries[..sample_size]
+        let sample_buckets = 250.min(self.size);
+        let total_slots = sample_buckets * SLOTS_PER_BUCKET;
+        let used: usize = self.buckets[..sample_buckets]
             .iter()
-            .filter(|e| e.key != 0)
+            .flat_map(|b| b.slots.iter())
+            .filter(|s| s.key.load(Ordering::Relaxed) != 0 || s.data.load(Ordering::Relaxed) != 0)
             .count();
-        (used * 1000) / sample_size
+        (used * 1000) / total_slots
     }
 }
 
@@ -11,12 +11,15 @@
 //! - `core` - Fundamental types: bitboards, board, moves, zobrist hashing
 //! - `engine` - Engine logic: move generation, evaluation, search
 //! - `uci` - UCI protocol implementation
+//! - `repr` - An earlier, standalone bitboard representation, kept around
+//!   for the integration tests written against it
 
 pub mod core;
 pub mod engine;
+pub mod repr;
 pub mod uci;
 
 // Re-export commonly used types at the crate root
 pub use core::{Bitboard, Board, CastlingRights, Color, Move, MoveList, Piece, PieceType, Square};
 pub use engine::{MoveGen, Searcher, SearchLimits};
-pub use uci::UCI;
+pub use uci::DuckUci;
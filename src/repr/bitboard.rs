@@ -1,6 +1,6 @@
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Bitboard(pub u64);
 
 // Bitboard is a 64-bit representation of a chess board
@@ -65,6 +65,54 @@ impl Bitboard {
             Some(63 - self.0.leading_zeros() as u8)
         }
     }
+
+    // True when more than one bit is set, without paying for a full
+    // `pop_count()`.
+    pub fn has_more_than_one(&self) -> bool {
+        self.0 & self.0.wrapping_sub(1) != 0
+    }
+
+    // True when every bit set in `self` is also set in `other`.
+    pub fn is_subset(&self, other: Bitboard) -> bool {
+        self.0 & other.0 == self.0
+    }
+
+    // True when every bit set in `other` is also set in `self`.
+    pub fn is_superset(&self, other: Bitboard) -> bool {
+        other.is_subset(*self)
+    }
+}
+
+impl FromIterator<u8> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut bb = Bitboard::EMPTY;
+        for square in iter {
+            bb.set_bit(square);
+        }
+        bb
+    }
+}
+
+/// Iterator over the set squares of a [`Bitboard`], least significant first.
+pub struct BitboardIter(Bitboard);
+
+impl Iterator for BitboardIter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sq = self.0.lsb()?;
+        self.0.clear_bit(sq);
+        Some(sq)
+    }
+}
+
+impl IntoIterator for Bitboard {
+    type Item = u8;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BitboardIter(self)
+    }
 }
 
 impl fmt::Display for Bitboard {
@@ -0,0 +1,9 @@
+//! Board representation primitives.
+//!
+//! This predates `core` and is kept around as the type the integration
+//! tests in `tests/bitboard_tests.rs` are written against; `core::bitboard`
+//! is the one the rest of the engine actually builds on.
+
+pub mod bitboard;
+
+pub use bitboard::Bitboard;
@@ -0,0 +1,303 @@
+//! Duck-chess UCI front-end, driving `core::board::Board`/`engine::movegen`
+//! directly. Advertises itself as a variant the way multi-variant engines
+//! (e.g. Fairy-Stockfish) do, so a variant-aware GUI knows to offer it.
+//!
+//! Implements the handshake subset a GUI needs: `uci`/`isready`,
+//! `position [fen ... | startpos] moves ...`, `go`, `stop`, and a `d`
+//! debug command that prints the board.
+
+use crate::core::board::{Board, Square};
+use crate::core::moves::{Move, MoveList};
+use crate::engine::movegen::MoveGen;
+use crate::engine::nnue::NNUEEvaluator;
+use crate::engine::search::{SearchLimits, Searcher};
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::Ordering;
+
+pub struct DuckUci {
+    board: Board,
+    /// Zobrist hash of the starting position and of every position reached
+    /// by the moves in the most recent `position ... moves ...` command,
+    /// oldest first. Fed to
+    /// [`Searcher::set_game_history`] before each `go` so a threefold
+    /// repetition spanning prior game moves (not just the current search
+    /// tree) is caught.
+    game_history: Vec<u64>,
+    /// NNUE evaluator backing the standalone `eval` command. Swappable at
+    /// runtime via the `EvalFile` option. Kept separate from `searcher`'s
+    /// own evaluator since `eval` reports a static score with no search.
+    evaluator: NNUEEvaluator,
+    /// Drives `go`; owns its own NNUE evaluator, Syzygy tablebase, and
+    /// `stop` flag, configured via the `SyzygyPath`/`SyzygyProbeLimit`/
+    /// search-tuning options handled below. `EvalFile` loads into
+    /// `evaluator` only; the searcher's evaluator keeps its built-in net.
+    searcher: Searcher,
+    /// Lazy SMP thread count, set via the `Threads` option and applied to
+    /// every `go` through [`SearchLimits::threads`].
+    threads: usize,
+}
+
+impl DuckUci {
+    pub fn new() -> Self {
+        DuckUci {
+            board: Board::startpos(),
+            game_history: Vec::new(),
+            evaluator: NNUEEvaluator::new(),
+            searcher: Searcher::new(),
+            threads: 1,
+        }
+    }
+
+    pub fn run(&mut self) {
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+
+        for line in stdin.lock().lines() {
+            let line = match line {
+                Ok(l) => l,
+                Err(_) => break,
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts[0] {
+                "uci" => self.cmd_uci(&mut stdout),
+                "isready" => writeln!(stdout, "readyok").unwrap(),
+                "ucinewgame" => {
+                    self.board = Board::startpos();
+                    self.game_history.clear();
+                    self.searcher.clear();
+                }
+                "position" => self.cmd_position(&parts),
+                "go" => self.cmd_go(&parts, &mut stdout),
+                "perft" => self.cmd_perft(&parts, &mut stdout),
+                "stop" => self.searcher.stop.store(true, Ordering::Relaxed),
+                "setoption" => self.cmd_setoption(&parts),
+                "d" | "debug" => writeln!(stdout, "{}", self.board).unwrap(),
+                "eval" => {
+                    let score = self.evaluator.evaluate_simple(&self.board);
+                    writeln!(stdout, "info string eval {} cp", score).unwrap();
+                }
+                "quit" => break,
+                _ => {}
+            }
+            stdout.flush().unwrap();
+        }
+    }
+
+    fn cmd_uci(&self, stdout: &mut io::Stdout) {
+        writeln!(stdout, "id name DuckChess 1.0.0").unwrap();
+        writeln!(stdout, "id author DuckChess Team").unwrap();
+        writeln!(
+            stdout,
+            "option name UCI_Variant type combo default duckchess var duckchess"
+        )
+        .unwrap();
+        writeln!(stdout, "option name EvalFile type string default").unwrap();
+        writeln!(stdout, "option name SyzygyPath type string default").unwrap();
+        writeln!(stdout, "option name SyzygyProbeLimit type spin default 7 min 0 max 7").unwrap();
+        writeln!(stdout, "option name Hash type spin default 64 min 1 max 4096").unwrap();
+        writeln!(stdout, "option name Threads type spin default 1 min 1 max 256").unwrap();
+        for line in Searcher::option_lines() {
+            writeln!(stdout, "{line}").unwrap();
+        }
+        writeln!(stdout, "uciok").unwrap();
+    }
+
+    /// Apply a `setoption name X value Y` command's already-split
+    /// arguments, including the leading `"setoption"` token.
+    ///
+    /// `EvalFile`, `SyzygyPath`, `SyzygyProbeLimit`, `Hash`, and `Threads`
+    /// are handled here; everything else falls through to
+    /// [`Searcher::set_option`]. Unknown names are ignored either way, per
+    /// the UCI spec. A path that fails to load leaves the current network
+    /// (synthetic or previously-loaded) in place.
+    pub fn cmd_setoption(&mut self, parts: &[&str]) {
+        let mut name = String::new();
+        let mut value = String::new();
+        let mut in_name = false;
+        let mut in_value = false;
+
+        for part in parts.iter().skip(1) {
+            match *part {
+                "name" => { in_name = true; in_value = false; }
+                "value" => { in_name = false; in_value = true; }
+                _ => {
+                    if in_name { if !name.is_empty() { name.push(' '); } name.push_str(part); }
+                    else if in_value { if !value.is_empty() { value.push(' '); } value.push_str(part); }
+                }
+            }
+        }
+
+        let opt = name.to_lowercase().replace([' ', '_'], "");
+        if opt == "evalfile" && !value.is_empty() {
+            let _ = self.evaluator.load_file(&value);
+        } else if opt == "syzygypath" {
+            self.searcher.set_syzygy_path(&value);
+        } else if opt == "syzygyprobelimit" {
+            if let Ok(n) = value.parse::<u32>() {
+                self.searcher.set_syzygy_probe_limit(n.min(7));
+            }
+        } else if opt == "hash" {
+            if let Ok(mb) = value.parse::<usize>() {
+                self.searcher.set_hash_size(mb);
+            }
+        } else if opt == "threads" {
+            if let Ok(n) = value.parse::<usize>() {
+                self.threads = n.max(1);
+            }
+        } else {
+            self.searcher.set_option(&name, &value);
+        }
+    }
+
+    /// Current position (for tests).
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Apply a `position ...` command's already-split arguments, including
+    /// the leading `"position"` token (used by tests and `run`'s dispatch).
+    pub fn cmd_position(&mut self, parts: &[&str]) {
+        let mut idx = 1;
+        if idx < parts.len() && parts[idx] == "startpos" {
+            self.board = Board::startpos();
+            idx += 1;
+        } else if idx < parts.len() && parts[idx] == "fen" {
+            idx += 1;
+            let mut fen_parts = Vec::new();
+            while idx < parts.len() && parts[idx] != "moves" {
+                fen_parts.push(parts[idx]);
+                idx += 1;
+            }
+            if let Ok(board) = Board::from_fen(&fen_parts.join(" ")) {
+                self.board = board;
+            }
+        }
+
+        self.game_history.clear();
+        self.game_history.push(self.board.hash);
+
+        if idx < parts.len() && parts[idx] == "moves" {
+            idx += 1;
+            for &mv_str in &parts[idx..] {
+                let legal = MoveGen::generate_legal_moves(&self.board);
+                if let Some(mv) = Self::parse_move(mv_str, &legal) {
+                    self.board.make_move(mv);
+                    self.game_history.push(self.board.hash);
+                }
+            }
+        }
+    }
+
+    /// Match a UCI token against the legal move list. Every duck-chess
+    /// move is paired with a duck relocation, so the full token is the
+    /// underlying move's UCI form followed by the duck's destination
+    /// square (e.g. `e2e4d4`); a caller that omits the duck square gets
+    /// the first legal move whose piece move matches.
+    fn parse_move(token: &str, legal: &MoveList) -> Option<Move> {
+        if let Some(mv) = legal.iter().find(|mv| Self::to_uci_with_duck(**mv) == token) {
+            return Some(*mv);
+        }
+
+        let base_len = if token.len() >= 5
+            && matches!(token.as_bytes().get(4), Some(b'n' | b'b' | b'r' | b'q'))
+        {
+            5
+        } else {
+            4
+        };
+        let base = token.get(..base_len)?;
+        let mv = legal.iter().find(|mv| mv.to_uci() == base).copied()?;
+        match token.get(base_len..).filter(|rest| !rest.is_empty()) {
+            Some(duck) => Square::from_algebraic(duck).map(|sq| mv.with_duck(sq)),
+            None => Some(mv),
+        }
+    }
+
+    /// Render a move as `<move><duck-square>`, or plain UCI if it carries
+    /// no duck relocation.
+    fn to_uci_with_duck(mv: Move) -> String {
+        match mv.duck_to() {
+            Some(duck) => format!("{}{}", mv.to_uci(), duck.to_algebraic()),
+            None => mv.to_uci(),
+        }
+    }
+
+    /// `perft [divide] <depth>`: count (or, with `divide`, break down by
+    /// root move) the leaf nodes `MoveGen::perft`/`perft_divide` reach from
+    /// the current position, and report timing/nps alongside the count —
+    /// a movegen regression check the way Stockfish's own `perft` does.
+    fn cmd_perft(&self, parts: &[&str], stdout: &mut io::Stdout) {
+        if parts.get(1).copied() == Some("divide") {
+            let depth: u32 = parts.get(2).and_then(|s| s.parse().ok()).unwrap_or(1);
+            let start = std::time::Instant::now();
+            let divided = MoveGen::perft_divide(&self.board, depth);
+            for (mv, nodes) in &divided {
+                writeln!(stdout, "{}: {}", Self::to_uci_with_duck(*mv), nodes).unwrap();
+            }
+            let total: u64 = divided.iter().map(|(_, nodes)| nodes).sum();
+            let elapsed = start.elapsed();
+            writeln!(stdout).unwrap();
+            writeln!(stdout, "Nodes searched: {} ({} ms)", total, elapsed.as_millis()).unwrap();
+            return;
+        }
+
+        let depth: u32 = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(1);
+        let start = std::time::Instant::now();
+        let nodes = MoveGen::perft(&self.board, depth);
+        let elapsed = start.elapsed();
+        let nps = if elapsed.as_millis() > 0 { nodes as u128 * 1000 / elapsed.as_millis() } else { 0 };
+        writeln!(stdout, "Nodes: {} ({} ms, {} nps)", nodes, elapsed.as_millis(), nps).unwrap();
+    }
+
+    /// Build [`SearchLimits`] from a `go ...` command's already-split
+    /// arguments, including the leading `"go"` token. Unrecognized tokens
+    /// (e.g. `searchmoves`) are ignored rather than rejected, per the UCI
+    /// spec's tolerance for unsupported-but-valid commands.
+    fn go_to_limits(&self, parts: &[&str]) -> SearchLimits {
+        let mut limits = SearchLimits { threads: Some(self.threads), ..SearchLimits::default() };
+        let mut i = 1;
+        while i < parts.len() {
+            let arg = parts.get(i + 1).copied();
+            match parts[i] {
+                "depth" => limits.depth = arg.and_then(|s| s.parse::<i32>().ok()),
+                "nodes" => limits.nodes = arg.and_then(|s| s.parse().ok()),
+                "movetime" => limits.movetime = arg.and_then(|s| s.parse().ok()),
+                "wtime" => limits.wtime = arg.and_then(|s| s.parse().ok()),
+                "btime" => limits.btime = arg.and_then(|s| s.parse().ok()),
+                "winc" => limits.winc = arg.and_then(|s| s.parse().ok()),
+                "binc" => limits.binc = arg.and_then(|s| s.parse().ok()),
+                "movestogo" => limits.movestogo = arg.and_then(|s| s.parse().ok()),
+                "infinite" => limits.infinite = true,
+                _ => {}
+            }
+            i += 1;
+        }
+        limits
+    }
+
+    fn cmd_go(&mut self, parts: &[&str], stdout: &mut io::Stdout) {
+        let limits = self.go_to_limits(parts);
+        // `set_game_history` wants the hashes up to, but not including, the
+        // position about to be searched; `game_history`'s last entry is
+        // that position itself.
+        let prior_len = self.game_history.len().saturating_sub(1);
+        self.searcher.set_game_history(self.game_history[..prior_len].to_vec());
+        let mv = self.searcher.search(&self.board, limits);
+        if mv.is_null() {
+            writeln!(stdout, "bestmove 0000").unwrap();
+        } else {
+            writeln!(stdout, "bestmove {}", Self::to_uci_with_duck(mv)).unwrap();
+        }
+    }
+}
+
+impl Default for DuckUci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
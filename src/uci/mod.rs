@@ -2,7 +2,7 @@
 //!
 //! This module implements the UCI protocol for communication with chess GUIs.
 
-pub mod protocol;
+pub mod duck;
 
-pub use protocol::UCI;
+pub use duck::DuckUci;
 
@@ -77,4 +77,23 @@ fn test_bitboard_lsb_msb() {
     bb.set_bit(63);
     assert_eq!(bb.lsb(), Some(0));
     assert_eq!(bb.msb(), Some(63));
+}
+
+#[test]
+fn test_bitboard_set_algebra() {
+    let mut bb = Bitboard::EMPTY;
+    assert!(!bb.has_more_than_one());
+    bb.set_bit(0);
+    assert!(!bb.has_more_than_one());
+    bb.set_bit(1);
+    assert!(bb.has_more_than_one());
+
+    let collected: Bitboard = [0u8, 1, 2].into_iter().collect();
+    assert_eq!(collected.pop_count(), 3);
+    assert!(collected.is_superset(bb));
+    assert!(bb.is_subset(collected));
+    assert!(!Bitboard::FULL.is_subset(bb));
+
+    let squares: Vec<u8> = collected.into_iter().collect();
+    assert_eq!(squares, vec![0, 1, 2]);
 }
\ No newline at end of file
@@ -143,6 +143,278 @@ fn test_fen_roundtrip() {
     }
 }
 
+#[test]
+fn test_chess960_shredder_fen_roundtrip() {
+    let fens = [
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        "rkrbnq1b/pp1pp1pp/n2bp3/1Npp4/2P5/8/PP1PPPPP/RKRBNQ1B w CAca - 2 5",
+    ];
+    for fen in fens {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.to_fen(), fen);
+    }
+}
+
+#[test]
+fn test_chess960_fen_accepts_standard_kqkq_alias() {
+    let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    assert_eq!(board.castling_mode, duck_chess::core::board::CastlingMode::Standard);
+    assert!(board.castling.can_castle_kingside(Color::White));
+}
+
+#[test]
+fn test_duck_fen_roundtrip() {
+    let without_duck = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    let board = Board::from_fen(without_duck).unwrap();
+    assert_eq!(board.duck, None);
+    assert_eq!(board.to_fen(), without_duck);
+
+    let with_duck = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1 d4";
+    let board = Board::from_fen(with_duck).unwrap();
+    assert_eq!(board.duck, Some(Square::D4));
+    assert_eq!(board.to_fen(), with_duck);
+}
+
+#[test]
+fn test_duck_fen_rejects_square_occupied_by_a_piece() {
+    let err = Board::from_fen(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 e2",
+    )
+    .unwrap_err();
+    assert_eq!(err, "Invalid FEN: the duck cannot share a square with a piece");
+}
+
+#[test]
+fn test_duck_fen_rejects_unparsable_square() {
+    let err = Board::from_fen(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 z9",
+    )
+    .unwrap_err();
+    assert_eq!(err, "Invalid FEN: invalid duck square 'z9'");
+}
+
+#[test]
+fn test_is_valid_accepts_legal_positions() {
+    let board = Board::startpos();
+    assert!(board.is_valid().is_ok());
+}
+
+#[test]
+fn test_is_valid_rejects_missing_king() {
+    use duck_chess::core::board::InvalidError;
+    let err = Board::from_fen("8/8/8/8/8/8/8/4K3 w - - 0 1").unwrap_err();
+    assert!(err.contains("Invalid FEN"));
+
+    let mut board = Board::startpos();
+    board.remove_piece(Square::E8);
+    assert_eq!(board.is_valid(), Err(InvalidError::MissingKing));
+}
+
+#[test]
+fn test_is_valid_rejects_pawn_on_back_rank() {
+    use duck_chess::core::board::InvalidError;
+    let mut board = Board::startpos();
+    board.remove_piece(Square::A2);
+    board.remove_piece(Square::A8);
+    board.put_piece(
+        duck_chess::core::board::Piece::new(PieceType::Pawn, Color::White),
+        Square::A8,
+    );
+    assert_eq!(board.is_valid(), Err(InvalidError::PawnOnBackRank));
+}
+
+#[test]
+fn test_is_valid_rejects_neighbouring_kings() {
+    use duck_chess::core::board::InvalidError;
+    let mut board = Board::empty();
+    board.put_piece(
+        duck_chess::core::board::Piece::new(PieceType::King, Color::White),
+        Square::E1,
+    );
+    board.put_piece(
+        duck_chess::core::board::Piece::new(PieceType::King, Color::Black),
+        Square::E2,
+    );
+    assert_eq!(board.is_valid(), Err(InvalidError::NeighbouringKings));
+}
+
+#[test]
+fn test_is_valid_rejects_opposite_check() {
+    let err = Board::from_fen("4k3/8/8/8/8/8/4Q3/K7 w - - 0 1").unwrap_err();
+    assert_eq!(err, "Invalid FEN: the side not to move is in check");
+}
+
+#[test]
+fn test_is_valid_rejects_bad_en_passant() {
+    let err = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e6 0 1")
+        .unwrap_err();
+    assert_eq!(err, "Invalid FEN: en passant square is inconsistent with the position");
+}
+
+#[test]
+fn test_board_builder_matches_from_fen() {
+    use duck_chess::core::board::BoardBuilder;
+
+    let from_fen = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+
+    let built = BoardBuilder::new()
+        .piece(Piece::new(PieceType::King, Color::Black), Square::E8)
+        .piece(Piece::new(PieceType::King, Color::White), Square::E1)
+        .piece(Piece::new(PieceType::Rook, Color::White), Square::H1)
+        .side_to_move(Color::White)
+        .castling(CastlingRights::new(CastlingRights::WHITE_KINGSIDE))
+        .build()
+        .unwrap();
+
+    assert_eq!(built.to_fen(), from_fen.to_fen());
+    assert_eq!(built.hash, from_fen.hash);
+    assert_eq!(built.pawn_hash, from_fen.pawn_hash);
+}
+
+#[test]
+fn test_board_builder_rejects_invalid_position() {
+    use duck_chess::core::board::{BoardBuilder, InvalidError};
+
+    let err = BoardBuilder::new()
+        .piece(Piece::new(PieceType::King, Color::White), Square::E1)
+        .piece(Piece::new(PieceType::King, Color::Black), Square::E2)
+        .build()
+        .unwrap_err();
+    assert_eq!(err, InvalidError::NeighbouringKings);
+}
+
+#[test]
+fn test_make_move_unchecked_unmake_restores_state() {
+    let board = Board::startpos();
+    let mut after = board.clone();
+    let mv = Move::new(Square::E2, Square::E4);
+
+    let undo = after.make_move_unchecked(mv);
+    assert_ne!(after.hash, board.hash);
+    assert!(after.en_passant.is_some());
+
+    after.unmake_move(mv, undo);
+    assert_eq!(after.hash, board.hash);
+    assert_eq!(after.en_passant, board.en_passant);
+    assert_eq!(after.castling, board.castling);
+    assert_eq!(after.piece_at, board.piece_at);
+}
+
+#[test]
+fn test_make_move_unchecked_lets_caller_test_legality() {
+    // The white king walks onto the black rook's file: making the move
+    // unconditionally and then probing with `is_attacked` is exactly the
+    // pattern `make_move` builds on top of.
+    let board = Board::from_fen("k3r3/8/8/8/8/8/8/3K4 w - - 0 1").unwrap();
+    let mut after = board.clone();
+    let mv = Move::new(Square::D1, Square::E1);
+
+    let undo = after.make_move_unchecked(mv);
+    assert!(after.is_attacked(after.king_square(Color::White), Color::Black));
+    after.unmake_move(mv, undo);
+    assert_eq!(after.piece_at, board.piece_at);
+}
+
+#[test]
+fn test_pawn_hash_tracks_only_pawns_and_kings() {
+    let board = Board::startpos();
+    assert_eq!(board.pawn_hash, board.calculate_pawn_hash());
+
+    // A knight move changes the main hash but not the pawn hash.
+    let mut after = board.clone();
+    let undo = after.make_move_unchecked(Move::new(Square::B1, Square::from_algebraic("c3").unwrap()));
+    assert_ne!(after.hash, board.hash);
+    assert_eq!(after.pawn_hash, board.pawn_hash);
+    assert_eq!(after.pawn_hash, after.calculate_pawn_hash());
+    after.unmake_move(Move::new(Square::B1, Square::from_algebraic("c3").unwrap()), undo);
+    assert_eq!(after.pawn_hash, board.pawn_hash);
+
+    // A pawn push changes both hashes.
+    let mut after = board.clone();
+    let mv = Move::new(Square::E2, Square::E4);
+    let undo = after.make_move_unchecked(mv);
+    assert_ne!(after.pawn_hash, board.pawn_hash);
+    assert_eq!(after.pawn_hash, after.calculate_pawn_hash());
+    after.unmake_move(mv, undo);
+    assert_eq!(after.pawn_hash, board.pawn_hash);
+}
+
+#[test]
+fn test_hash_tracks_duck_square() {
+    let board = Board::startpos();
+    assert_eq!(board.zobrist(), board.calculate_hash());
+
+    // Placing the duck changes the hash, and unmaking restores it.
+    let mut after = board.clone();
+    let mv = Move::new(Square::E2, Square::E4).with_duck(Square::D4);
+    let undo = after.make_move_unchecked(mv);
+    assert_ne!(after.hash, board.hash);
+    assert_eq!(after.zobrist(), after.calculate_hash());
+    after.unmake_move(mv, undo);
+    assert_eq!(after.hash, board.hash);
+
+    // Relocating the duck to a different square is itself a hash change,
+    // distinct from the move it's paired with.
+    let mut without_duck_move = board.clone();
+    without_duck_move.make_move_unchecked(Move::new(Square::E2, Square::E4));
+    let mut with_duck_move = board.clone();
+    with_duck_move.make_move_unchecked(mv);
+    assert_ne!(without_duck_move.hash, with_duck_move.hash);
+}
+
+#[test]
+fn test_render_pretty_includes_duck_and_flips() {
+    let mut board = Board::startpos();
+    board.duck = Some(Square::D4);
+
+    let normal = board.render_pretty(false);
+    assert!(normal.contains('♔'));
+    assert!(normal.contains('◉'));
+    assert!(normal.contains(" a "));
+
+    let flipped = board.render_pretty(true);
+    assert_ne!(normal, flipped);
+    assert!(flipped.contains(" h "));
+
+    assert_eq!(format!("{:#}", board), board.render_pretty(false));
+}
+
+#[test]
+fn test_pinned_detects_sliding_pin() {
+    // Black rook on e8 pins the white knight on e4 to the white king on e1.
+    let board = Board::from_fen("k3r3/8/8/8/4N3/8/8/4K3 w - - 0 1").unwrap();
+    let pinned = board.pinned(Color::White);
+    assert!(pinned.contains(Square::E4.0));
+    assert_eq!(pinned.count(), 1);
+}
+
+#[test]
+fn test_pinned_empty_when_no_pin() {
+    let board = Board::startpos();
+    assert!(board.pinned(Color::White).is_empty());
+    assert!(board.pinned(Color::Black).is_empty());
+}
+
+#[test]
+fn test_make_move_rejects_move_that_exposes_pinned_piece() {
+    let mut board = Board::from_fen("k3r3/8/8/8/4N3/8/8/4K3 w - - 0 1").unwrap();
+    // The pinned knight has no legal moves off the e-file.
+    let mv = Move::new(Square::E4, Square::from_algebraic("d6").unwrap());
+    assert!(board.make_move(mv).is_none());
+}
+
+#[test]
+fn test_make_move_allows_unpinned_move_without_full_scan() {
+    // The bishop on f1 isn't pinned, isn't a king move, isn't en passant,
+    // and white isn't in check: `make_move` should accept it without
+    // running its full post-move `is_attacked` scan.
+    let mut board = Board::from_fen("k3r3/8/8/8/4N3/8/8/4KB2 w - - 0 1").unwrap();
+    let mv = Move::new(Square::F1, Square::from_algebraic("e2").unwrap());
+    assert!(board.make_move(mv).is_some());
+}
+
 #[test]
 fn test_piece_counts() {
     let board = Board::startpos();
@@ -171,6 +443,56 @@ fn test_insufficient_material() {
     assert!(!board.is_insufficient_material());
 }
 
+#[test]
+fn test_terminal_state_standard_checkmate_and_stalemate() {
+    use duck_chess::core::board::TerminalState;
+
+    // Fool's mate: white to move, mated.
+    let board =
+        Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+    assert_eq!(board.terminal_state(&[]), TerminalState::Win(Color::Black));
+
+    // Classic stalemate: black to move, no legal moves, not in check.
+    let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(board.terminal_state(&[]), TerminalState::Draw);
+
+    // Insufficient material still applies under the standard variant.
+    let board = Board::from_fen("8/8/8/4k3/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(board.terminal_state(&[]), TerminalState::Draw);
+}
+
+#[test]
+fn test_terminal_state_duck_chess_king_capture_and_no_moves() {
+    use duck_chess::core::board::{TerminalState, Variant};
+
+    // Black's king is simply gone, as if just captured: an immediate win
+    // for White, no check or mate machinery involved.
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    board.variant = Variant::DuckChess;
+    board.remove_piece(Square::E8);
+    assert_eq!(board.terminal_state(&[]), TerminalState::Win(Color::White));
+
+    // A side to move with no legal move loses outright under duck chess,
+    // with no stalemate concept.
+    let mut board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    board.variant = Variant::DuckChess;
+    assert_eq!(board.terminal_state(&[]), TerminalState::Win(Color::White));
+}
+
+#[test]
+fn test_terminal_state_fifty_move_and_repetition() {
+    use duck_chess::core::board::TerminalState;
+
+    let mut board = Board::from_fen("8/8/8/4k3/8/8/8/4KR2 w - - 99 80").unwrap();
+    board.halfmove_clock = 100;
+    assert_eq!(board.terminal_state(&[]), TerminalState::Draw);
+
+    let board = Board::from_fen("8/8/8/4k3/8/8/8/4KR2 w - - 0 1").unwrap();
+    let history = vec![board.hash, 0xDEAD, board.hash, 0xBEEF, board.hash];
+    assert_eq!(board.terminal_state(&history), TerminalState::Draw);
+    assert_eq!(board.terminal_state(&history[..2]), TerminalState::Ongoing);
+}
+
 // ============================================================================
 // Move Tests
 // ============================================================================
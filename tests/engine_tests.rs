@@ -140,8 +140,8 @@ fn test_nnue_accumulator_refresh() {
 fn test_nnue_feature_index() {
     let piece = Piece::new(PieceType::Pawn, Color::White);
     let sq = Square::E4;
-    let white_idx = NNUENetwork::feature_index(piece, sq, Color::White);
-    let black_idx = NNUENetwork::feature_index(piece, sq, Color::Black);
+    let white_idx = NNUENetwork::feature_index(piece, sq, Square::E1, Color::White);
+    let black_idx = NNUENetwork::feature_index(piece, sq, Square::E8, Color::Black);
     assert_ne!(white_idx, black_idx);
 }
 
@@ -225,3 +225,30 @@ fn test_search_time_management() {
     assert!(time.unwrap().as_millis() > 0);
     assert!(time.unwrap().as_millis() <= 30000);
 }
+
+#[test]
+fn test_search_stable_recapture_cuts_time_short() {
+    // White's rook hangs to an undefended black queen; every other legal
+    // move is clearly worse, so the best move and score should settle
+    // within the first few iterations. With a long clock, adaptive time
+    // management should cut the search well short of the full per-move
+    // allocation instead of burning the whole budget chasing a result
+    // that's already decided.
+    let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+    let mut searcher = Searcher::new();
+    let limits = SearchLimits {
+        wtime: Some(60000),
+        btime: Some(60000),
+        winc: Some(1000),
+        binc: Some(1000),
+        ..Default::default()
+    };
+    let base_target = searcher.calculate_time(&limits, Color::White).unwrap();
+
+    let start = std::time::Instant::now();
+    let mv = searcher.search(&board, limits);
+    let elapsed = start.elapsed();
+
+    assert!(!mv.is_null());
+    assert!(elapsed < base_target);
+}
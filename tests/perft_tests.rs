@@ -7,23 +7,9 @@
 use duck_chess::core::board::Board;
 use duck_chess::engine::movegen::MoveGen;
 
-/// Perft function - counts leaf nodes at a given depth
+/// Counts leaf nodes at a given depth using the engine's built-in perft.
 fn perft(board: &Board, depth: u32) -> u64 {
-    if depth == 0 {
-        return 1;
-    }
-
-    let moves = MoveGen::generate_legal_moves(board);
-    let mut nodes = 0;
-
-    for mv in moves.iter() {
-        let mut new_board = board.clone();
-        if new_board.make_move(*mv) {
-            nodes += perft(&new_board, depth - 1);
-        }
-    }
-
-    nodes
+    MoveGen::perft(board, depth)
 }
 
 /// Perft for starting position
@@ -57,6 +43,12 @@ fn test_perft_startpos_depth_5() {
     assert_eq!(perft(&board, 5), 4865609);
 }
 
+#[test]
+fn test_perft_startpos_depth_6() {
+    let board = Board::startpos();
+    assert_eq!(perft(&board, 6), 119060324);
+}
+
 /// Kiwipete position - famous test position with many edge cases
 /// r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -
 #[test]
@@ -234,3 +226,14 @@ fn test_pinned_piece() {
     assert!(bishop_moves.len() <= 3);
 }
 
+/// `perft_divide` localizes a node-count mismatch to a single root move;
+/// its per-move counts should sum back to the plain `perft` total.
+#[test]
+fn test_perft_divide_matches_perft_total() {
+    let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let divided = MoveGen::perft_divide(&board, 3);
+
+    assert_eq!(divided.len(), 48);
+    assert_eq!(divided.iter().map(|&(_, nodes)| nodes).sum::<u64>(), perft(&board, 3));
+}
+
@@ -184,11 +184,51 @@ fn test_depth_limit() {
     };
     
     let mv = searcher.search(&board, limits);
-    
+
     // Should find a valid move
     assert!(!mv.is_null());
 }
 
+#[test]
+fn test_lazy_smp_finds_legal_move() {
+    // `threads > 1` should route through `search_parallel`, and the move
+    // the pool settles on should still be one of the root's legal moves.
+    let board = Board::startpos();
+    let mut searcher = Searcher::new();
+    let limits = SearchLimits {
+        depth: Some(6),
+        threads: Some(4),
+        ..Default::default()
+    };
+
+    let mv = searcher.search(&board, limits);
+
+    use duck_chess::engine::movegen::MoveGen;
+    let legal_moves = MoveGen::generate_legal_moves(&board);
+    assert!(
+        legal_moves.iter().any(|&m| m.raw() == mv.raw()),
+        "Lazy SMP search returned a move not in the root's legal move list"
+    );
+}
+
+#[test]
+fn test_lazy_smp_completes_requested_depth() {
+    // The main thread still drives iterative deepening itself while a
+    // pool of helpers searches alongside it, so a depth-limited search
+    // should complete that depth same as the single-threaded path.
+    let board = Board::startpos();
+    let mut searcher = Searcher::new();
+    let limits = SearchLimits {
+        depth: Some(5),
+        threads: Some(4),
+        ..Default::default()
+    };
+
+    searcher.search(&board, limits);
+
+    assert_eq!(searcher.stats.completed_depth, 5);
+}
+
 // ============================================================================
 // Evaluation Tests
 // ============================================================================
@@ -2,7 +2,7 @@
 //!
 //! These tests verify the UCI protocol implementation works correctly.
 
-use duck_chess::core::board::Board;
+use duck_chess::core::board::{Board, Square};
 
 // ============================================================================
 // FEN Parsing Tests
@@ -219,7 +219,56 @@ fn test_zobrist_different_positions() {
 fn test_zobrist_same_position() {
     let board1 = Board::startpos();
     let board2 = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
-    
+
     assert_eq!(board1.hash, board2.hash);
 }
 
+// ============================================================================
+// Duck-Chess UCI Front-End Tests
+// ============================================================================
+
+#[test]
+fn test_duck_uci_position_startpos() {
+    use duck_chess::uci::DuckUci;
+
+    let mut uci = DuckUci::new();
+    uci.cmd_position(&["position", "startpos"]);
+    assert_eq!(uci.board().to_fen(), Board::startpos().to_fen());
+}
+
+#[test]
+fn test_duck_uci_position_fen_with_duck() {
+    use duck_chess::uci::DuckUci;
+
+    let mut uci = DuckUci::new();
+    uci.cmd_position(&[
+        "position", "fen", "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR", "b", "KQkq", "e3",
+        "0", "1", "d4",
+    ]);
+    assert_eq!(uci.board().duck, Some(Square::D4));
+}
+
+#[test]
+fn test_duck_uci_position_applies_moves() {
+    use duck_chess::uci::DuckUci;
+
+    let mut uci = DuckUci::new();
+    uci.cmd_position(&["position", "startpos", "moves", "e2e4d4"]);
+    assert_eq!(uci.board().piece_at[Square::E4.index()].unwrap().to_char(), 'P');
+    assert_eq!(uci.board().duck, Some(Square::D4));
+}
+
+#[test]
+fn test_duck_uci_setoption_evalfile_bad_path_is_ignored() {
+    use duck_chess::uci::DuckUci;
+
+    // A missing EvalFile shouldn't panic or otherwise break the engine;
+    // it just leaves the built-in synthetic weights in place.
+    let mut uci = DuckUci::new();
+    uci.cmd_setoption(&[
+        "setoption", "name", "EvalFile", "value", "/nonexistent/net.nnue",
+    ]);
+    uci.cmd_position(&["position", "startpos"]);
+    assert_eq!(uci.board().to_fen(), Board::startpos().to_fen());
+}
+